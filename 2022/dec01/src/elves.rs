@@ -0,0 +1,124 @@
+use std::{
+    fmt,
+    io::{self, BufRead, Lines},
+};
+
+#[derive(Debug)]
+pub struct Elf {
+    pub index: usize,
+    pub items: usize,
+    pub total: u64,
+    pub min: u64,
+    pub max: u64,
+}
+
+impl Elf {
+    fn new(index: usize) -> Elf {
+        Elf {
+            index,
+            items: 0,
+            total: 0,
+            min: u64::MAX,
+            max: 0,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+    Io(io::Error),
+    Malformed { line: usize, text: String },
+    InvalidByte { line: usize, byte: u8 },
+    Overflow { line: usize },
+    /// The answer adds up several elves' totals and that sum overflows.
+    SumOverflow,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Io(e) => write!(f, "read error: {}", e),
+            ParseError::Malformed { line, text } => {
+                write!(f, "line {}: expected a calorie count, got {:?}", line, text)
+            }
+            ParseError::InvalidByte { line, byte } => write!(
+                f,
+                "line {}: unexpected byte '{}' in calorie count",
+                line,
+                byte.escape_ascii()
+            ),
+            ParseError::Overflow { line } => {
+                write!(f, "line {}: calorie total overflows u64", line)
+            }
+            ParseError::SumOverflow => write!(f, "sum of calorie totals overflows u64"),
+        }
+    }
+}
+
+/// Reads blank-line separated groups of calorie counts, one `Elf` per group.
+/// The last group is closed at end of input even without a trailing blank line.
+pub struct Elves<R> {
+    lines: Lines<R>,
+    line_no: usize,
+    count: usize,
+}
+
+impl<R: BufRead> Elves<R> {
+    pub fn new(input: R) -> Elves<R> {
+        Elves {
+            lines: input.lines(),
+            line_no: 0,
+            count: 0,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Elves<R> {
+    type Item = Result<Elf, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut current: Option<Elf> = None;
+
+        for line in self.lines.by_ref() {
+            self.line_no += 1;
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => return Some(Err(ParseError::Io(e))),
+            };
+
+            // Same grammar as the byte scanner: digits, optionally surrounded
+            // by ASCII spaces, tabs and carriage returns.
+            let text = line.trim_matches([' ', '\t', '\r']);
+            if text.is_empty() {
+                if current.is_some() {
+                    return current.map(Ok);
+                }
+                continue;
+            }
+
+            if !text.bytes().all(|b| b.is_ascii_digit()) {
+                return Some(Err(ParseError::Malformed {
+                    line: self.line_no,
+                    text: line,
+                }));
+            }
+            let calories = match str::parse::<u64>(text) {
+                Ok(calories) => calories,
+                Err(_) => return Some(Err(ParseError::Overflow { line: self.line_no })),
+            };
+            let elf = current.get_or_insert_with(|| {
+                self.count += 1;
+                Elf::new(self.count)
+            });
+            match elf.total.checked_add(calories) {
+                Some(total) => elf.total = total,
+                None => return Some(Err(ParseError::Overflow { line: self.line_no })),
+            }
+            elf.items += 1;
+            elf.min = u64::min(elf.min, calories);
+            elf.max = u64::max(elf.max, calories);
+        }
+
+        current.map(Ok)
+    }
+}
@@ -1,25 +1,189 @@
-use std::{cmp::Ordering, io::stdin};
+mod elves;
+mod report;
+mod stream;
+mod teams;
 
-fn main() {
-    let mut top3 = vec![0, 0, 0];
-    let mut current = 0u32;
-
-    for line in stdin().lines().flatten() {
-        if let Ok(calories) = str::parse::<u32>(&line) {
-            current += calories
-        } else {
-            top3.push(current);
-            top3.sort_by(|a, b| {
-                if *a < *b {
-                    Ordering::Greater
-                } else {
-                    Ordering::Less
-                }
-            });
-            top3.pop();
-            current = 0;
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    env::args,
+    io::{stdin, BufRead, BufReader},
+    process::exit,
+    time::Instant,
+};
+
+use elves::{Elf, Elves, ParseError};
+use report::Format;
+use teams::Solver;
+
+enum Mode {
+    Top(usize),
+    Stream(usize),
+    Bench(usize),
+    Report(Format),
+    Teams(usize, Option<Solver>),
+}
+
+fn parse_args() -> Mode {
+    let args: Vec<String> = args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("stream") => Mode::Stream(
+            args.get(1)
+                .map(|n| str::parse(n).expect("N must be an integer"))
+                .unwrap_or(3),
+        ),
+        Some("bench") => Mode::Bench(
+            args.get(1)
+                .map(|mb| str::parse(mb).expect("size must be a number of MB"))
+                .unwrap_or(256),
+        ),
+        Some("report") => Mode::Report(
+            args.get(1)
+                .map(|s| Format::from_str(s).expect("format must be text, csv or json"))
+                .unwrap_or(Format::Text),
+        ),
+        Some("teams") => Mode::Teams(
+            args.get(1)
+                .and_then(|k| str::parse(k).ok())
+                .filter(|&k| k > 0)
+                .expect("K must be a positive integer"),
+            args.get(2)
+                .map(|s| Solver::from_str(s).expect("solver must be exact, kk or greedy")),
+        ),
+        Some(n) => Mode::Top(str::parse(n).expect("N must be an integer")),
+        None => Mode::Top(3),
+    }
+}
+
+struct TopN {
+    n: usize,
+    heap: BinaryHeap<Reverse<u64>>,
+}
+
+impl TopN {
+    fn new(n: usize) -> TopN {
+        TopN {
+            n,
+            heap: BinaryHeap::with_capacity(n + 1),
         }
     }
 
-    println!("{}", top3.iter().sum::<u32>());
+    fn push(&mut self, total: u64) {
+        self.heap.push(Reverse(total));
+        if self.heap.len() > self.n {
+            self.heap.pop();
+        }
+    }
+
+    fn sum(&self) -> Result<u64, ParseError> {
+        self.heap
+            .iter()
+            .try_fold(0u64, |sum, Reverse(x)| sum.checked_add(*x))
+            .ok_or(ParseError::SumOverflow)
+    }
+}
+
+fn read_elves() -> impl Iterator<Item = Elf> {
+    Elves::new(stdin().lock()).map(|elf| {
+        elf.unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            exit(1);
+        })
+    })
+}
+
+/// Returns the part 1 and top-N answers, reading `input` either line by line
+/// or with the byte-level scanner.
+fn top<R: BufRead>(input: R, n: usize, bytes: bool) -> Result<(u64, u64), ParseError> {
+    let mut top1 = TopN::new(1);
+    let mut top = TopN::new(n);
+    let mut push = |total| {
+        top1.push(total);
+        top.push(total);
+    };
+
+    if bytes {
+        stream::scan(input, &mut push)?;
+    } else {
+        for elf in Elves::new(input) {
+            push(elf?.total);
+        }
+    }
+
+    Ok((top1.sum()?, top.sum()?))
+}
+
+fn print_top(n: usize, bytes: bool) {
+    let start = Instant::now();
+    let mut input = stream::Counter::new(BufReader::with_capacity(1 << 20, stdin()));
+    match top(&mut input, n, bytes) {
+        Ok((part1, part2)) => {
+            println!("{}", part1);
+            println!("{}", part2);
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            exit(1);
+        }
+    }
+
+    if !bytes {
+        return;
+    }
+    let secs = start.elapsed().as_secs_f64();
+    let size = input.bytes as f64 / (1 << 20) as f64;
+    eprintln!(
+        "read {:.1} MB in {:.3}s ({:.1} MB/s)",
+        size,
+        secs,
+        size / secs
+    );
+}
+
+fn bench(mb: usize) {
+    let data = stream::synthetic(mb << 20, &mut stream::Rng(2022));
+    let size = data.len() as f64 / (1 << 20) as f64;
+    println!("generated {:.1} MB", size);
+
+    let mut answers = vec![];
+    for (name, bytes) in [("lines", false), ("bytes", true)] {
+        let start = Instant::now();
+        let answer = top(&data[..], 3, bytes).expect("synthetic input is valid");
+        let secs = start.elapsed().as_secs_f64();
+        println!(
+            "{}: {:?} in {:.3}s ({:.1} MB/s)",
+            name,
+            answer,
+            secs,
+            size / secs
+        );
+        answers.push(answer);
+    }
+
+    if answers[0] != answers[1] {
+        eprintln!("line and byte readers disagree");
+        exit(1);
+    }
+}
+
+fn main() {
+    match parse_args() {
+        Mode::Top(n) => print_top(n, false),
+        Mode::Stream(n) => print_top(n, true),
+        Mode::Bench(mb) => bench(mb),
+        Mode::Report(format) => {
+            if let Err(e) = report::print(&read_elves().collect::<Vec<_>>(), format) {
+                eprintln!("{}", e);
+                exit(1);
+            }
+        }
+        Mode::Teams(k, solver) => {
+            let elves: Vec<Elf> = read_elves().collect();
+            let solver = solver.unwrap_or_else(|| Solver::for_size(elves.len()));
+            if let Err(e) = teams::print(&elves, k, solver) {
+                eprintln!("{}", e);
+                exit(1);
+            }
+        }
+    }
 }
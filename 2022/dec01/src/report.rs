@@ -0,0 +1,211 @@
+use crate::elves::{Elf, ParseError};
+
+const PERCENTILES: &[u32] = &[10, 25, 75, 90, 99];
+const BUCKETS: u64 = 10;
+
+#[derive(Clone, Copy)]
+pub enum Format {
+    Text,
+    Csv,
+    Json,
+}
+
+impl Format {
+    pub fn from_str(s: &str) -> Option<Format> {
+        match s {
+            "text" => Some(Format::Text),
+            "csv" => Some(Format::Csv),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+}
+
+struct Bucket {
+    from: u64,
+    to: u64,
+    count: usize,
+}
+
+struct Summary {
+    count: usize,
+    sum: u64,
+    mean: f64,
+    median: f64,
+    percentiles: Vec<(u32, u64)>,
+    histogram: Vec<Bucket>,
+}
+
+impl Summary {
+    fn new(elves: &[Elf]) -> Result<Option<Summary>, ParseError> {
+        if elves.is_empty() {
+            return Ok(None);
+        }
+
+        let mut totals: Vec<u64> = elves.iter().map(|e| e.total).collect();
+        totals.sort();
+
+        let count = totals.len();
+        let sum = totals
+            .iter()
+            .try_fold(0u64, |sum, &t| sum.checked_add(t))
+            .ok_or(ParseError::SumOverflow)?;
+        let median = if count.is_multiple_of(2) {
+            (totals[count / 2 - 1] as f64 + totals[count / 2] as f64) / 2.0
+        } else {
+            totals[count / 2] as f64
+        };
+
+        // Nearest-rank percentiles.
+        let percentiles = PERCENTILES
+            .iter()
+            .map(|&p| {
+                let rank = (p as usize * count).div_ceil(100).max(1);
+                (p, totals[rank - 1])
+            })
+            .collect();
+
+        let lo = totals[0];
+        let hi = totals[count - 1];
+        let width = (hi - lo) / BUCKETS + 1;
+        // Bucket bounds are clamped to u64::MAX for totals close to it.
+        let bound = |x: u128| x.min(u64::MAX as u128) as u64;
+        let mut histogram: Vec<Bucket> = (0..BUCKETS)
+            .map(|i| Bucket {
+                from: bound(lo as u128 + i as u128 * width as u128),
+                to: bound(lo as u128 + (i + 1) as u128 * width as u128 - 1),
+                count: 0,
+            })
+            .collect();
+        for t in &totals {
+            histogram[((t - lo) / width) as usize].count += 1;
+        }
+
+        Ok(Some(Summary {
+            count,
+            sum,
+            mean: sum as f64 / count as f64,
+            median,
+            percentiles,
+            histogram,
+        }))
+    }
+}
+
+/// Competition ranking by total, largest first: equal totals share a rank.
+fn ranks(elves: &[Elf]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..elves.len()).collect();
+    order.sort_by_key(|&i| std::cmp::Reverse(elves[i].total));
+
+    let mut ranks = vec![0; elves.len()];
+    for (pos, &i) in order.iter().enumerate() {
+        ranks[i] = if pos > 0 && elves[order[pos - 1]].total == elves[i].total {
+            ranks[order[pos - 1]]
+        } else {
+            pos + 1
+        };
+    }
+    ranks
+}
+
+fn print_text(elves: &[Elf], ranks: &[usize], summary: &Option<Summary>) {
+    println!(
+        "{:>6} {:>6} {:>8} {:>8} {:>8} {:>6}",
+        "elf", "items", "total", "min", "max", "rank"
+    );
+    for (e, rank) in elves.iter().zip(ranks) {
+        println!(
+            "{:>6} {:>6} {:>8} {:>8} {:>8} {:>6}",
+            e.index, e.items, e.total, e.min, e.max, rank
+        );
+    }
+
+    let s = match summary {
+        Some(s) => s,
+        None => return,
+    };
+    println!();
+    println!("elves:  {}", s.count);
+    println!("sum:    {}", s.sum);
+    println!("mean:   {:.2}", s.mean);
+    println!("median: {:.1}", s.median);
+    for (p, v) in &s.percentiles {
+        println!("p{:<5}  {}", p, v);
+    }
+
+    println!();
+    let widest = s
+        .histogram
+        .iter()
+        .map(|b| b.count)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    for b in &s.histogram {
+        let bar = "#".repeat(b.count * 40 / widest);
+        println!("{:>8} - {:>8} {:>5} {}", b.from, b.to, b.count, bar);
+    }
+}
+
+fn print_csv(elves: &[Elf], ranks: &[usize]) {
+    println!("elf,items,total,min,max,rank");
+    for (e, rank) in elves.iter().zip(ranks) {
+        println!(
+            "{},{},{},{},{},{}",
+            e.index, e.items, e.total, e.min, e.max, rank
+        );
+    }
+}
+
+fn print_json(elves: &[Elf], ranks: &[usize], summary: &Option<Summary>) {
+    println!("{{");
+    println!("  \"elves\": [");
+    for (i, (e, rank)) in elves.iter().zip(ranks).enumerate() {
+        let sep = if i + 1 < elves.len() { "," } else { "" };
+        println!(
+            "    {{\"elf\": {}, \"items\": {}, \"total\": {}, \"min\": {}, \"max\": {}, \"rank\": {}}}{}",
+            e.index, e.items, e.total, e.min, e.max, rank, sep
+        );
+    }
+    print!("  ]");
+
+    if let Some(s) = summary {
+        println!(",");
+        println!("  \"summary\": {{");
+        println!("    \"elves\": {},", s.count);
+        println!("    \"sum\": {},", s.sum);
+        println!("    \"mean\": {},", s.mean);
+        println!("    \"median\": {},", s.median);
+        let percentiles: Vec<String> = s
+            .percentiles
+            .iter()
+            .map(|(p, v)| format!("\"p{}\": {}", p, v))
+            .collect();
+        println!("    \"percentiles\": {{{}}},", percentiles.join(", "));
+        let buckets: Vec<String> = s
+            .histogram
+            .iter()
+            .map(|b| {
+                format!(
+                    "{{\"from\": {}, \"to\": {}, \"count\": {}}}",
+                    b.from, b.to, b.count
+                )
+            })
+            .collect();
+        println!("    \"histogram\": [{}]", buckets.join(", "));
+        println!("  }}");
+    } else {
+        println!();
+    }
+    println!("}}");
+}
+
+pub fn print(elves: &[Elf], format: Format) -> Result<(), ParseError> {
+    let ranks = ranks(elves);
+    match format {
+        Format::Text => print_text(elves, &ranks, &Summary::new(elves)?),
+        Format::Csv => print_csv(elves, &ranks),
+        Format::Json => print_json(elves, &ranks, &Summary::new(elves)?),
+    }
+    Ok(())
+}
@@ -0,0 +1,206 @@
+use std::io::{self, BufRead, ErrorKind, Read};
+
+use crate::elves::ParseError;
+
+/// Byte-level equivalent of `Elves` that only keeps the running state of the
+/// current line and group, so memory use doesn't depend on the input.
+struct Scanner {
+    line: usize,
+    value: u64,
+    digits: bool,
+    trailing: bool,
+    total: u64,
+    in_group: bool,
+}
+
+impl Scanner {
+    fn new() -> Scanner {
+        Scanner {
+            line: 1,
+            value: 0,
+            digits: false,
+            trailing: false,
+            total: 0,
+            in_group: false,
+        }
+    }
+
+    fn feed(&mut self, buf: &[u8], on_elf: &mut impl FnMut(u64)) -> Result<(), ParseError> {
+        for &b in buf {
+            match b {
+                b'0'..=b'9' if !self.trailing => {
+                    self.value = self
+                        .value
+                        .checked_mul(10)
+                        .and_then(|v| v.checked_add((b - b'0') as u64))
+                        .ok_or(ParseError::Overflow { line: self.line })?;
+                    self.digits = true;
+                }
+                b' ' | b'\t' | b'\r' => self.trailing = self.digits,
+                b'\n' => self.end_line(on_elf)?,
+                _ => {
+                    return Err(ParseError::InvalidByte {
+                        line: self.line,
+                        byte: b,
+                    })
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn end_line(&mut self, on_elf: &mut impl FnMut(u64)) -> Result<(), ParseError> {
+        if self.digits {
+            self.total = self
+                .total
+                .checked_add(self.value)
+                .ok_or(ParseError::Overflow { line: self.line })?;
+            self.in_group = true;
+        } else if self.in_group {
+            on_elf(self.total);
+            self.total = 0;
+            self.in_group = false;
+        }
+
+        self.line += 1;
+        self.value = 0;
+        self.digits = false;
+        self.trailing = false;
+        Ok(())
+    }
+
+    fn finish(mut self, on_elf: &mut impl FnMut(u64)) -> Result<(), ParseError> {
+        self.end_line(on_elf)?;
+        self.end_line(on_elf)
+    }
+}
+
+/// Calls `on_elf` with the total of every group in `input`.
+pub fn scan<R: BufRead>(mut input: R, mut on_elf: impl FnMut(u64)) -> Result<(), ParseError> {
+    let mut scanner = Scanner::new();
+
+    loop {
+        let buf = match input.fill_buf() {
+            Ok(buf) => buf,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(ParseError::Io(e)),
+        };
+        if buf.is_empty() {
+            break;
+        }
+        scanner.feed(buf, &mut on_elf)?;
+
+        let n = buf.len();
+        input.consume(n);
+    }
+
+    scanner.finish(&mut on_elf)
+}
+
+/// Counts the bytes consumed from the wrapped reader.
+pub struct Counter<R> {
+    inner: R,
+    pub bytes: u64,
+}
+
+impl<R> Counter<R> {
+    pub fn new(inner: R) -> Counter<R> {
+        Counter { inner, bytes: 0 }
+    }
+}
+
+impl<R: Read> Read for Counter<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.bytes += n as u64;
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for Counter<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.bytes += amt as u64;
+        self.inner.consume(amt)
+    }
+}
+
+pub struct Rng(pub u64);
+
+impl Rng {
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 % n
+    }
+}
+
+/// Generates roughly `size` bytes of puzzle-shaped input.
+pub fn synthetic(size: usize, rng: &mut Rng) -> Vec<u8> {
+    let mut out = Vec::with_capacity(size + 64);
+    while out.len() < size {
+        for _ in 0..=rng.below(15) {
+            out.extend_from_slice((1000 + rng.below(59000)).to_string().as_bytes());
+            out.push(b'\n');
+        }
+        out.push(b'\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elves::Elves;
+
+    fn lines(input: &str) -> Option<Vec<u64>> {
+        Elves::new(input.as_bytes())
+            .map(|elf| elf.map(|e| e.total))
+            .collect::<Result<_, _>>()
+            .ok()
+    }
+
+    fn bytes(input: &str) -> Option<Vec<u64>> {
+        let mut totals = vec![];
+        scan(input.as_bytes(), |t| totals.push(t)).ok()?;
+        Some(totals)
+    }
+
+    #[test]
+    fn readers_agree() {
+        let cases = [
+            "",
+            "\n\n",
+            "1\n2\n\n3",
+            "1\n2\n\n3\n\n",
+            " 1 \t\r\n\t2\r\n\r\n 3",
+            "1\n \t\n2\n",
+            "+5\n",
+            "-5\n",
+            "5\x0b\n",
+            "\x0c5\n",
+            "1 2\n",
+            "12a\n",
+            "5\u{a0}\n",
+            "18446744073709551615\n",
+            "18446744073709551616\n",
+            "18446744073709551615\n1\n",
+            "18446744073709551615\n\n1\n",
+        ];
+        for input in cases {
+            assert_eq!(lines(input), bytes(input), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn readers_agree_on_synthetic_input() {
+        let data = synthetic(1 << 16, &mut Rng(2022));
+        let input = String::from_utf8(data).unwrap();
+        assert!(lines(&input).is_some());
+        assert_eq!(lines(&input), bytes(&input));
+    }
+}
@@ -0,0 +1,411 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+};
+
+use crate::elves::{Elf, ParseError};
+
+/// Inputs up to this many elves are solved exactly when no solver is given.
+const EXACT_LIMIT: usize = 16;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Solver {
+    Exact,
+    KarmarkarKarp,
+    Greedy,
+}
+
+impl Solver {
+    pub fn from_str(s: &str) -> Option<Solver> {
+        match s {
+            "exact" => Some(Solver::Exact),
+            "kk" => Some(Solver::KarmarkarKarp),
+            "greedy" => Some(Solver::Greedy),
+            _ => None,
+        }
+    }
+
+    pub fn for_size(n: usize) -> Solver {
+        if n <= EXACT_LIMIT {
+            Solver::Exact
+        } else {
+            Solver::KarmarkarKarp
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Partition {
+    /// Sum and 1-based elf indices of every team.
+    pub teams: Vec<(u64, Vec<usize>)>,
+}
+
+impl Partition {
+    fn empty(k: usize) -> Partition {
+        Partition {
+            teams: vec![(0, vec![]); k],
+        }
+    }
+
+    fn spread(teams: &[(u64, Vec<usize>)]) -> u64 {
+        let max = teams.iter().map(|t| t.0).max().unwrap_or(0);
+        let min = teams.iter().map(|t| t.0).min().unwrap_or(0);
+        max - min
+    }
+
+    pub fn gap(&self) -> u64 {
+        Self::spread(&self.teams)
+    }
+}
+
+/// Fails if the calories of all elves together overflow u64; otherwise no
+/// team sum can overflow either.
+pub fn solve(elves: &[Elf], k: usize, solver: Solver) -> Result<Partition, ParseError> {
+    elves
+        .iter()
+        .try_fold(0u64, |sum, e| sum.checked_add(e.total))
+        .ok_or(ParseError::SumOverflow)?;
+
+    let mut items: Vec<(u64, usize)> = elves.iter().map(|e| (e.total, e.index)).collect();
+    items.sort_by(|a, b| b.cmp(a));
+
+    let mut p = match solver {
+        Solver::Exact => exact(&items, k),
+        Solver::KarmarkarKarp => karmarkar_karp(&items, k),
+        Solver::Greedy => greedy(&items, k),
+    };
+    p.teams.sort_by_key(|t| Reverse(t.0));
+    Ok(p)
+}
+
+/// Longest-processing-time first: each elf joins the currently lightest team.
+fn greedy(items: &[(u64, usize)], k: usize) -> Partition {
+    let mut p = Partition::empty(k);
+    for &(total, index) in items {
+        let team = p.teams.iter_mut().min_by_key(|t| t.0).unwrap();
+        team.0 += total;
+        team.1.push(index);
+    }
+    p
+}
+
+/// A partial k-way partition, ordered by the spread between its largest and
+/// smallest team so the heap always merges the most uneven ones first.
+#[derive(PartialEq, Eq)]
+struct Tuple {
+    spread: u64,
+    teams: Vec<(u64, Vec<usize>)>,
+}
+
+impl Ord for Tuple {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.spread.cmp(&other.spread)
+    }
+}
+
+impl PartialOrd for Tuple {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Multiway largest differencing method: repeatedly combine the two most
+/// uneven partial partitions, pairing the largest team of one with the
+/// smallest team of the other.
+fn karmarkar_karp(items: &[(u64, usize)], k: usize) -> Partition {
+    let mut heap: BinaryHeap<Tuple> = items
+        .iter()
+        .map(|&(total, index)| {
+            let mut teams = vec![(0, vec![]); k];
+            teams[0] = (total, vec![index]);
+            Tuple {
+                spread: total,
+                teams,
+            }
+        })
+        .collect();
+
+    while heap.len() > 1 {
+        let a = heap.pop().unwrap();
+        let b = heap.pop().unwrap();
+        let mut teams: Vec<(u64, Vec<usize>)> = a
+            .teams
+            .into_iter()
+            .zip(b.teams.into_iter().rev())
+            .map(|((sa, mut ma), (sb, mb))| {
+                ma.extend(mb);
+                (sa + sb, ma)
+            })
+            .collect();
+        teams.sort_by_key(|t| Reverse(t.0));
+        heap.push(Tuple {
+            spread: Partition::spread(&teams),
+            teams,
+        });
+    }
+
+    heap.pop()
+        .map(|t| Partition { teams: t.teams })
+        .unwrap_or_else(|| Partition::empty(k))
+}
+
+struct Search<'a> {
+    items: &'a [(u64, usize)],
+    /// `remaining[i]` is the sum of `items[i..]`.
+    remaining: Vec<u64>,
+    /// No partition can have a smaller gap than this.
+    floor: u64,
+    sums: Vec<u64>,
+    assignment: Vec<usize>,
+    best_gap: u64,
+    best: Vec<usize>,
+}
+
+impl Search<'_> {
+    fn run(&mut self, pos: usize) {
+        if self.best_gap == self.floor {
+            return;
+        }
+
+        let max = *self.sums.iter().max().unwrap();
+        let min = *self.sums.iter().min().unwrap();
+
+        if pos == self.items.len() {
+            if max - min < self.best_gap {
+                self.best_gap = max - min;
+                self.best = self.assignment.clone();
+            }
+            return;
+        }
+
+        // Bound the final smallest team from above: it can at most absorb
+        // everything that is left, and can't exceed an even share of the total
+        // or of what the largest team leaves for the others. The final largest
+        // team is bounded from below the same way.
+        let k = self.sums.len() as u64;
+        let others = u64::max(k - 1, 1);
+        let total = self.remaining[0];
+        let low = (min + self.remaining[pos])
+            .min(total / k)
+            .min((total - max) / others);
+        let high = u64::max(max, (total - low).div_ceil(others));
+        if high.saturating_sub(low) >= self.best_gap {
+            return;
+        }
+
+        let calories = self.items[pos].0;
+        for team in 0..self.sums.len() {
+            // Teams with equal sums are interchangeable; only try the first.
+            if self.sums[..team].contains(&self.sums[team]) {
+                continue;
+            }
+            self.sums[team] += calories;
+            self.assignment[pos] = team;
+            self.run(pos + 1);
+            self.sums[team] -= calories;
+        }
+    }
+}
+
+/// Depth-first branch and bound over team assignments, seeded with the
+/// Karmarkar-Karp result as the initial upper bound.
+fn exact(items: &[(u64, usize)], k: usize) -> Partition {
+    let seed = karmarkar_karp(items, k);
+    if k == 0 {
+        return seed;
+    }
+
+    let mut remaining = vec![0; items.len() + 1];
+    for i in (0..items.len()).rev() {
+        remaining[i] = remaining[i + 1] + items[i].0;
+    }
+    let total = remaining[0];
+
+    let mut search = Search {
+        items,
+        floor: if total.is_multiple_of(k as u64) || items.len() < k {
+            0
+        } else {
+            1
+        },
+        remaining,
+        sums: vec![0; k],
+        assignment: vec![0; items.len()],
+        best_gap: seed.gap(),
+        best: vec![],
+    };
+    search.run(0);
+
+    if search.best.is_empty() {
+        return seed;
+    }
+
+    let mut p = Partition::empty(k);
+    for (&(total, index), &team) in items.iter().zip(&search.best) {
+        p.teams[team].0 += total;
+        p.teams[team].1.push(index);
+    }
+    p
+}
+
+pub fn print(elves: &[Elf], k: usize, solver: Solver) -> Result<(), ParseError> {
+    let p = solve(elves, k, solver)?;
+
+    println!("solver: {:?}", solver);
+    for (i, (sum, members)) in p.teams.iter().enumerate() {
+        let mut members = members.clone();
+        members.sort();
+        let names: Vec<String> = members.iter().map(|m| m.to_string()).collect();
+        println!("team {}: {} ({})", i + 1, sum, names.join(", "));
+    }
+    println!("gap: {}", p.gap());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elves::Elves;
+
+    fn elves(totals: &[u64]) -> Vec<Elf> {
+        totals
+            .iter()
+            .enumerate()
+            .map(|(i, &total)| Elf {
+                index: i + 1,
+                items: 1,
+                total,
+                min: total,
+                max: total,
+            })
+            .collect()
+    }
+
+    fn input_totals() -> Vec<u64> {
+        let input = include_str!("../input");
+        Elves::new(input.as_bytes())
+            .map(|elf| elf.unwrap().total)
+            .collect()
+    }
+
+    /// The smallest gap over all k^n assignments.
+    fn brute_force(totals: &[u64], k: usize) -> u64 {
+        let mut best = u64::MAX;
+        let mut assignment = vec![0; totals.len()];
+        loop {
+            let mut sums = vec![0; k];
+            for (&total, &team) in totals.iter().zip(&assignment) {
+                sums[team] += total;
+            }
+            best = best.min(Partition::spread(
+                &sums.into_iter().map(|s| (s, vec![])).collect::<Vec<_>>(),
+            ));
+
+            let mut i = 0;
+            while i < assignment.len() && assignment[i] == k - 1 {
+                assignment[i] = 0;
+                i += 1;
+            }
+            if i == assignment.len() {
+                return best;
+            }
+            assignment[i] += 1;
+        }
+    }
+
+    /// Every elf is in exactly one team and every team sum matches its members.
+    fn assert_valid(elves: &[Elf], k: usize, p: &Partition) {
+        assert_eq!(p.teams.len(), k);
+        let mut seen = vec![0; elves.len()];
+        for (sum, members) in &p.teams {
+            for &m in members {
+                seen[m - 1] += 1;
+            }
+            assert_eq!(*sum, members.iter().map(|&m| elves[m - 1].total).sum());
+        }
+        assert!(seen.iter().all(|&n| n == 1), "elves assigned {:?}", seen);
+    }
+
+    /// Checks all solvers on `totals`, returning the exact gap.
+    fn check(totals: &[u64], k: usize) -> u64 {
+        let elves = elves(totals);
+        let exact = solve(&elves, k, Solver::Exact).unwrap();
+        assert_valid(&elves, k, &exact);
+        for solver in [Solver::KarmarkarKarp, Solver::Greedy] {
+            let p = solve(&elves, k, solver).unwrap();
+            assert_valid(&elves, k, &p);
+            assert!(
+                p.gap() >= exact.gap(),
+                "{:?} beats exact on {:?} with k = {}",
+                solver,
+                totals,
+                k
+            );
+        }
+        exact.gap()
+    }
+
+    #[test]
+    fn exact_matches_brute_force_on_small_inputs() {
+        let cases: &[&[u64]] = &[
+            &[],
+            &[5],
+            &[3, 3],
+            &[8, 7, 6, 5, 4],
+            &[4, 5, 6, 7, 8, 9, 10],
+            &[1, 1, 1, 1, 1, 1, 100],
+            &[31, 27, 19, 18, 17, 12, 9, 2],
+        ];
+        for totals in cases {
+            for k in 1..=4 {
+                assert_eq!(
+                    check(totals, k),
+                    brute_force(totals, k),
+                    "{:?} with k = {}",
+                    totals,
+                    k
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn exact_matches_brute_force_on_input_totals() {
+        let totals = input_totals();
+        for chunk in totals.chunks(9).take(12) {
+            for k in 2..=3 {
+                assert_eq!(
+                    check(chunk, k),
+                    brute_force(chunk, k),
+                    "{:?} with k = {}",
+                    chunk,
+                    k
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn heuristics_assign_every_input_elf() {
+        let elves = elves(&input_totals());
+        for k in [2, 3, 7] {
+            for solver in [Solver::KarmarkarKarp, Solver::Greedy] {
+                assert_valid(&elves, k, &solve(&elves, k, solver).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn solvers_reject_totals_that_overflow() {
+        for solver in [Solver::Exact, Solver::KarmarkarKarp, Solver::Greedy] {
+            let p = solve(&elves(&[u64::MAX]), 2, solver).unwrap();
+            assert_eq!(p.gap(), u64::MAX);
+            for k in 1..=3 {
+                assert!(matches!(
+                    solve(&elves(&[u64::MAX, 1]), k, solver),
+                    Err(ParseError::SumOverflow)
+                ));
+            }
+        }
+    }
+}
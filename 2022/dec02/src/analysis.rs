@@ -0,0 +1,77 @@
+use crate::rules::{Interpretation, Rules, HAND};
+
+/// Best, expected random and worst score of a single round against `o`.
+fn bounds(rules: &Rules, o: HAND) -> (u64, f64, u64) {
+    let scores: Vec<u64> = rules.hands().map(|y| rules.score(o, y)).collect();
+    let best = *scores.iter().max().unwrap();
+    let worst = *scores.iter().min().unwrap();
+    let mean = scores.iter().sum::<u64>() as f64 / scores.len() as f64;
+    (best, mean, worst)
+}
+
+fn best_hand(rules: &Rules, o: HAND) -> HAND {
+    rules.hands().max_by_key(|&y| rules.score(o, y)).unwrap()
+}
+
+pub fn print(rules: &Rules, guide: &[(char, char)]) {
+    let mut best = 0;
+    let mut random = 0.0;
+    let mut worst = 0;
+    for &(o, _) in guide {
+        let o = rules.opponent_hand(o).expect("invalid opponent char");
+        let (b, r, w) = bounds(rules, o);
+        best += b;
+        random += r;
+        worst += w;
+    }
+
+    println!("optimal: {}", best);
+    println!("random:  {:.1}", random);
+    println!("worst:   {}", worst);
+
+    for interpretation in Interpretation::ALL {
+        println!();
+
+        let guide_sum: Option<u64> = guide
+            .iter()
+            .map(|&(o, y)| rules.points(o, y, interpretation))
+            .sum();
+        let guide_sum = match guide_sum {
+            Some(sum) => sum,
+            None => {
+                println!(
+                    "{:?}: guide doesn't fit this interpretation",
+                    interpretation
+                );
+                continue;
+            }
+        };
+        println!(
+            "{:?}: {} ({:.1}% of optimal)",
+            interpretation,
+            guide_sum,
+            100.0 * guide_sum as f64 / best.max(1) as f64
+        );
+
+        for (round, &(oc, yc)) in guide.iter().enumerate() {
+            let o = rules.opponent_hand(oc).unwrap();
+            let y = rules.my_hand(o, yc, interpretation).unwrap();
+            let b = best_hand(rules, o);
+            let lost = rules.score(o, b) - rules.score(o, y);
+            if lost > 0 {
+                println!(
+                    "  round {}: {} {}: {} vs {} scores {}, {} would score {} (-{})",
+                    round + 1,
+                    oc,
+                    yc,
+                    rules.names[y.0],
+                    rules.names[o.0],
+                    rules.score(o, y),
+                    rules.names[b.0],
+                    rules.score(o, b),
+                    lost
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use crate::{guide::ParseError, rules::Rules};
+
+const GAME_NAMES: [&str; 3] = ["loss", "draw", "win"];
+
+/// Applies a scoring rules file with one `name = points` entry per line, where
+/// `name` is a shape of the game or one of `loss`, `draw` and `win`. Blank
+/// lines and `#` comments are ignored, and names that aren't listed keep
+/// their default points.
+pub fn apply(rules: &mut Rules, text: &str) -> Result<(), ParseError> {
+    let mut shape_points = rules.shape_points().to_vec();
+    let mut game_points = rules.game_points();
+
+    for (idx, line) in text.lines().enumerate() {
+        let error = |reason: String| ParseError {
+            line: idx + 1,
+            text: line.to_string(),
+            reason,
+        };
+
+        let entry = line.split('#').next().unwrap().trim();
+        if entry.is_empty() {
+            continue;
+        }
+
+        let (name, value) = entry
+            .split_once('=')
+            .ok_or_else(|| error("expected `name = points`".to_string()))?;
+        let name = name.trim();
+        let points: u32 = str::parse(value.trim())
+            .map_err(|_| error(format!("invalid points {:?}", value.trim())))?;
+
+        if let Some(i) = rules.names.iter().position(|n| *n == name) {
+            shape_points[i] = points;
+        } else if let Some(i) = GAME_NAMES.iter().position(|n| *n == name) {
+            game_points[i] = points;
+        } else {
+            return Err(error(format!("unknown shape or outcome {:?}", name)));
+        }
+    }
+
+    rules.set_points(shape_points, game_points);
+    Ok(())
+}
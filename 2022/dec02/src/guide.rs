@@ -0,0 +1,57 @@
+use std::{fmt, io::BufRead};
+
+use crate::rules::Rules;
+
+#[derive(Debug)]
+pub struct ParseError {
+    pub line: usize,
+    pub text: String,
+    pub reason: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}: {:?}", self.line, self.reason, self.text)
+    }
+}
+
+/// Reads a strategy guide where every non-empty line is exactly an opponent
+/// character, a single space and a response character.
+pub fn parse<R: BufRead>(rules: &Rules, input: R) -> Result<Vec<(char, char)>, ParseError> {
+    let mut guide = vec![];
+
+    for (idx, line) in input.lines().enumerate() {
+        let error = |text: &str, reason: &str| ParseError {
+            line: idx + 1,
+            text: text.to_string(),
+            reason: reason.to_string(),
+        };
+
+        let line = line.map_err(|e| error("", &e.to_string()))?;
+        let text = line.strip_suffix('\r').unwrap_or(&line);
+        if text.is_empty() {
+            continue;
+        }
+
+        let chars: Vec<char> = text.chars().collect();
+        let (o, y) = match chars[..] {
+            [o, ' ', y] => (o, y),
+            _ => {
+                return Err(error(
+                    text,
+                    "expected an opponent and a response separated by one space",
+                ))
+            }
+        };
+        if !rules.opponent_chars.contains(&o) {
+            return Err(error(text, &format!("unknown opponent {:?}", o)));
+        }
+        if !rules.hand_chars.contains(&y) && !rules.game_chars.contains(&y) {
+            return Err(error(text, &format!("unknown response {:?}", y)));
+        }
+
+        guide.push((o, y));
+    }
+
+    Ok(guide)
+}
@@ -1,91 +1,103 @@
-use std::io::stdin;
+#![allow(clippy::upper_case_acronyms)]
 
-const RPS_ABC: &[char] = &['A', 'B', 'C'];
+mod analysis;
+mod config;
+mod guide;
+mod mappings;
+mod markov;
+mod rules;
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
-enum GAME {
-    LOSS,
-    DRAW,
-    WIN,
-}
+use std::{env::args, fs, io::stdin, process::exit};
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
-enum HAND {
-    ROCK,
-    PAPER,
-    SCISSORS,
-}
+use rules::{Interpretation, Rules};
 
-fn idx_to_hand(i: usize) -> HAND {
-    match i {
-        0 => HAND::ROCK,
-        1 => HAND::PAPER,
-        2 => HAND::SCISSORS,
-        _ => panic!("invalid hand idx"),
-    }
+enum Mode {
+    Score,
+    Analyze,
+    Markov,
+    Mappings,
 }
 
-fn char_to_game(c: char) -> GAME {
-    match c {
-        'X' => GAME::LOSS,
-        'Y' => GAME::DRAW,
-        'Z' => GAME::WIN,
-        _ => panic!("invalid hand char"),
+/// `dec02 [analyze|markov|mappings] [rps|rpsls|rps7] [OPPONENT_CHARS HAND_CHARS [GAME_CHARS]]
+/// [--points FILE]`
+fn parse_args() -> (Mode, Rules) {
+    let mut args: Vec<String> = args().skip(1).collect();
+    let points_file = args.iter().position(|a| a == "--points").map(|i| {
+        args.remove(i);
+        assert!(i < args.len(), "--points needs a file");
+        args.remove(i)
+    });
+
+    let mode = match args.first().map(String::as_str) {
+        Some("analyze") => Some(Mode::Analyze),
+        Some("markov") => Some(Mode::Markov),
+        Some("mappings") => Some(Mode::Mappings),
+        _ => None,
+    };
+    if mode.is_some() {
+        args.remove(0);
     }
-}
+    let mode = mode.unwrap_or(Mode::Score);
 
-fn game(o: HAND, y: HAND) -> GAME {
-    match (o, y) {
-        (HAND::ROCK, HAND::SCISSORS) => GAME::LOSS,
-        (HAND::SCISSORS, HAND::ROCK) => GAME::WIN,
-        _ => {
-            if o < y {
-                GAME::WIN
-            } else if o > y {
-                GAME::LOSS
-            } else {
-                GAME::DRAW
-            }
-        }
+    let mut rules = Rules::preset(args.first().map(String::as_str).unwrap_or("rps"))
+        .expect("game must be rps, rpsls or rps7");
+
+    if let Some(chars) = args.get(1) {
+        rules.opponent_chars = chars.chars().collect();
     }
-}
+    if let Some(chars) = args.get(2) {
+        rules.hand_chars = chars.chars().collect();
+    }
+    if let Some(chars) = args.get(3) {
+        rules.game_chars = chars.chars().collect();
+    }
+    assert_eq!(
+        rules.opponent_chars.len(),
+        rules.len(),
+        "one opponent char per shape"
+    );
+    assert_eq!(
+        rules.hand_chars.len(),
+        rules.len(),
+        "one hand char per shape"
+    );
+    assert_eq!(rules.game_chars.len(), 3, "one game char per outcome");
 
-fn find_my_hand(o: HAND, g: GAME) -> HAND {
-    for i in 0..=2 {
-        let h = idx_to_hand(i);
-        if game(o, h) == g {
-            return h;
+    if let Some(path) = points_file {
+        let text = fs::read_to_string(&path).expect("failed to read points file");
+        if let Err(e) = config::apply(&mut rules, &text) {
+            eprintln!("{}: {}", path, e);
+            exit(1);
         }
     }
-    panic!("failed to find matching hand")
-}
 
-fn points(opponent: char, game_char: char) -> u32 {
-    let (oi, _) = RPS_ABC
-        .iter()
-        .enumerate()
-        .find(|(_, x)| x.eq(&&opponent))
-        .unwrap();
-    let o = idx_to_hand(oi);
-    let g = char_to_game(game_char);
-    let y = find_my_hand(o, g);
+    (mode, rules)
+}
 
-    match g {
-        GAME::LOSS => y as u32 + 1 + 0,
-        GAME::DRAW => y as u32 + 1 + 3,
-        GAME::WIN => y as u32 + 1 + 6,
+fn score(rules: &Rules, guide: &[(char, char)]) {
+    for interpretation in Interpretation::ALL {
+        let sum: Option<u64> = guide
+            .iter()
+            .map(|&(o, y)| rules.points(o, y, interpretation))
+            .sum();
+        match sum {
+            Some(sum) => println!("{}", sum),
+            None => println!("n/a"),
+        }
     }
 }
 
 fn main() {
-    let mut sum = 0;
+    let (mode, rules) = parse_args();
+    let guide = guide::parse(&rules, stdin().lock()).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        exit(1);
+    });
 
-    for line in stdin().lines().flatten() {
-        if let Some(o) = line.chars().next() {
-            let y = line.chars().last().unwrap();
-            sum += points(o, y);
-        }
+    match mode {
+        Mode::Score => score(&rules, &guide),
+        Mode::Analyze => analysis::print(&rules, &guide),
+        Mode::Markov => markov::print(&rules, &guide),
+        Mode::Mappings => mappings::print(&rules, &guide),
     }
-
-    println!("{}", sum)
 }
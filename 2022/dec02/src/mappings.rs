@@ -0,0 +1,87 @@
+use crate::rules::{Interpretation, Rules};
+
+fn permutations(items: &[char]) -> Vec<Vec<char>> {
+    if items.len() <= 1 {
+        return vec![items.to_vec()];
+    }
+
+    let mut out = vec![];
+    for (i, &first) in items.iter().enumerate() {
+        let mut rest = items.to_vec();
+        rest.remove(i);
+        for mut p in permutations(&rest) {
+            p.insert(0, first);
+            out.push(p);
+        }
+    }
+    out
+}
+
+fn column_chars(rules: &Rules, interpretation: Interpretation) -> &Vec<char> {
+    match interpretation {
+        Interpretation::Shape => &rules.hand_chars,
+        Interpretation::Outcome => &rules.game_chars,
+    }
+}
+
+fn describe(rules: &Rules, interpretation: Interpretation) -> String {
+    let targets: Vec<String> = match interpretation {
+        Interpretation::Shape => rules.names.iter().map(|n| n.to_string()).collect(),
+        Interpretation::Outcome => ["loss", "draw", "win"].map(String::from).to_vec(),
+    };
+    let mut pairs: Vec<(char, String)> = column_chars(rules, interpretation)
+        .iter()
+        .copied()
+        .zip(targets)
+        .collect();
+    pairs.sort();
+    pairs
+        .iter()
+        .map(|(c, t)| format!("{}={}", c, t))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Scores the guide under every assignment of the second column's characters
+/// to shapes and to outcomes, best first. The configured mapping is marked
+/// with a `*`.
+pub fn print(rules: &Rules, guide: &[(char, char)]) {
+    let mut results = vec![];
+
+    for interpretation in Interpretation::ALL {
+        let chars = column_chars(rules, interpretation);
+
+        for p in permutations(chars) {
+            let current = &p == chars;
+            let mut mapped = rules.clone();
+            match interpretation {
+                Interpretation::Shape => mapped.hand_chars = p,
+                Interpretation::Outcome => mapped.game_chars = p,
+            }
+
+            let sum: Option<u64> = guide
+                .iter()
+                .map(|&(o, y)| mapped.points(o, y, interpretation))
+                .sum();
+            if let Some(sum) = sum {
+                results.push((
+                    sum,
+                    interpretation,
+                    describe(&mapped, interpretation),
+                    current,
+                ));
+            }
+        }
+    }
+
+    results.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.2.cmp(&b.2)));
+    for (sum, interpretation, mapping, current) in results {
+        println!(
+            "{:>8} {:<8} {}{}",
+            sum,
+            format!("{:?}", interpretation),
+            mapping,
+            if current { " *" } else { "" }
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+use crate::rules::{Interpretation, Rules, GAME, HAND};
+
+const ORDERS: &[usize] = &[0, 1, 2, 3, 4, 6, 8];
+
+/// Online order-k Markov model of the opponent's moves. Contexts that haven't
+/// been seen yet back off to the longest shorter context that has.
+struct Model {
+    k: usize,
+    history: Vec<usize>,
+    counts: Vec<HashMap<Vec<usize>, Vec<u32>>>,
+}
+
+impl Model {
+    fn new(k: usize) -> Model {
+        Model {
+            k,
+            history: vec![],
+            counts: vec![HashMap::new(); k + 1],
+        }
+    }
+
+    fn context(&self, order: usize) -> Option<&[usize]> {
+        let len = self.history.len();
+        (len >= order).then(|| &self.history[len - order..])
+    }
+
+    fn predict(&self) -> Option<&Vec<u32>> {
+        (0..=self.k)
+            .rev()
+            .find_map(|order| self.counts[order].get(self.context(order)?))
+    }
+
+    fn observe(&mut self, o: HAND, n: usize) {
+        for order in 0..=self.k {
+            if let Some(context) = self.context(order) {
+                let context = context.to_vec();
+                self.counts[order]
+                    .entry(context)
+                    .or_insert_with(|| vec![0; n])[o.0] += 1;
+            }
+        }
+        self.history.push(o.0);
+    }
+}
+
+/// The hand with the best expected score against the predicted move counts.
+fn best_reply(rules: &Rules, counts: Option<&Vec<u32>>) -> HAND {
+    let uniform = vec![1; rules.len()];
+    let counts = counts.unwrap_or(&uniform);
+    rules
+        .hands()
+        .max_by_key(|&y| {
+            rules
+                .hands()
+                .map(|o| counts[o.0] as u64 * rules.score(o, y))
+                .sum::<u64>()
+        })
+        .unwrap()
+}
+
+pub fn print(rules: &Rules, guide: &[(char, char)]) {
+    for interpretation in Interpretation::ALL {
+        let sum: Option<u64> = guide
+            .iter()
+            .map(|&(o, y)| rules.points(o, y, interpretation))
+            .sum();
+        if let Some(sum) = sum {
+            println!("guide ({:?}): {}", interpretation, sum);
+        }
+    }
+
+    for &k in ORDERS {
+        let mut model = Model::new(k);
+        let mut sum = 0;
+        let mut games = [0; 3];
+
+        for &(oc, _) in guide {
+            let o = rules.opponent_hand(oc).expect("invalid opponent char");
+            let y = best_reply(rules, model.predict());
+            sum += rules.score(o, y);
+            games[rules.game(o, y) as usize] += 1;
+            model.observe(o, rules.len());
+        }
+
+        println!(
+            "k={}: {} (won {}, drew {}, lost {})",
+            k,
+            sum,
+            games[GAME::WIN as usize],
+            games[GAME::DRAW as usize],
+            games[GAME::LOSS as usize]
+        );
+    }
+}
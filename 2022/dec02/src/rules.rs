@@ -0,0 +1,178 @@
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum GAME {
+    LOSS,
+    DRAW,
+    WIN,
+}
+
+/// Index of a shape in the cyclic order of its `Rules`.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub struct HAND(pub usize);
+
+/// How the second column of the strategy guide is read.
+#[derive(Clone, Copy, Debug)]
+pub enum Interpretation {
+    /// The column is the shape to play.
+    Shape,
+    /// The column is the outcome to aim for.
+    Outcome,
+}
+
+impl Interpretation {
+    pub const ALL: [Interpretation; 2] = [Interpretation::Shape, Interpretation::Outcome];
+}
+
+/// A cyclic game with an odd number of shapes, where every shape beats the
+/// (n - 1) / 2 shapes before it and loses to the (n - 1) / 2 after it.
+#[derive(Clone)]
+pub struct Rules {
+    pub names: Vec<&'static str>,
+    pub opponent_chars: Vec<char>,
+    pub hand_chars: Vec<char>,
+    pub game_chars: Vec<char>,
+    shape_points: Vec<u32>,
+    game_points: [u32; 3],
+    outcomes: Vec<Vec<GAME>>,
+    /// Kept in u64 so that no choice of points can overflow a round or a
+    /// guide total.
+    scores: Vec<Vec<u64>>,
+}
+
+impl Rules {
+    pub fn cyclic(names: &[&'static str]) -> Rules {
+        let n = names.len();
+        assert!(n % 2 == 1, "cyclic games need an odd number of shapes");
+
+        let outcomes: Vec<Vec<GAME>> = (0..n)
+            .map(|o| {
+                (0..n)
+                    .map(|y| match (y + n - o) % n {
+                        0 => GAME::DRAW,
+                        d if d <= n / 2 => GAME::WIN,
+                        _ => GAME::LOSS,
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut rules = Rules {
+            names: names.to_vec(),
+            opponent_chars: ('A'..).take(n).collect(),
+            hand_chars: (char::from(b'Z' + 1 - n as u8)..='Z').collect(),
+            game_chars: vec!['X', 'Y', 'Z'],
+            shape_points: vec![],
+            game_points: [0; 3],
+            outcomes,
+            scores: vec![],
+        };
+        rules.set_points((1..=n as u32).collect(), [0, 3, 6]);
+        rules
+    }
+
+    pub fn shape_points(&self) -> &[u32] {
+        &self.shape_points
+    }
+
+    pub fn game_points(&self) -> [u32; 3] {
+        self.game_points
+    }
+
+    /// Replaces the points for playing each shape and for each outcome, and
+    /// rebuilds the score table from them.
+    pub fn set_points(&mut self, shape_points: Vec<u32>, game_points: [u32; 3]) {
+        assert_eq!(shape_points.len(), self.len(), "one score per shape");
+        self.scores = self
+            .outcomes
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .enumerate()
+                    .map(|(y, g)| shape_points[y] as u64 + game_points[*g as usize] as u64)
+                    .collect()
+            })
+            .collect();
+        self.shape_points = shape_points;
+        self.game_points = game_points;
+    }
+
+    pub fn preset(name: &str) -> Option<Rules> {
+        match name {
+            "rps" => Some(Rules::cyclic(&["rock", "paper", "scissors"])),
+            "rpsls" => Some(Rules::cyclic(&[
+                "rock", "spock", "paper", "lizard", "scissors",
+            ])),
+            "rps7" => Some(Rules::cyclic(&[
+                "rock", "water", "air", "paper", "sponge", "scissors", "fire",
+            ])),
+            _ => None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn hands(&self) -> impl Iterator<Item = HAND> {
+        (0..self.len()).map(HAND)
+    }
+
+    pub fn idx_to_hand(&self, i: usize) -> HAND {
+        if i >= self.len() {
+            panic!("invalid hand idx")
+        }
+        HAND(i)
+    }
+
+    pub fn opponent_hand(&self, c: char) -> Option<HAND> {
+        let i = self.opponent_chars.iter().position(|x| *x == c)?;
+        Some(self.idx_to_hand(i))
+    }
+
+    pub fn char_to_hand(&self, c: char) -> Option<HAND> {
+        let i = self.hand_chars.iter().position(|x| *x == c)?;
+        Some(self.idx_to_hand(i))
+    }
+
+    pub fn char_to_game(&self, c: char) -> Option<GAME> {
+        match self.game_chars.iter().position(|x| *x == c)? {
+            0 => Some(GAME::LOSS),
+            1 => Some(GAME::DRAW),
+            _ => Some(GAME::WIN),
+        }
+    }
+
+    pub fn game(&self, o: HAND, y: HAND) -> GAME {
+        self.outcomes[o.0][y.0]
+    }
+
+    pub fn score(&self, o: HAND, y: HAND) -> u64 {
+        self.scores[o.0][y.0]
+    }
+
+    pub fn find_my_hand(&self, o: HAND, g: GAME) -> HAND {
+        self.hands()
+            .find(|&h| self.game(o, h) == g)
+            .expect("failed to find matching hand")
+    }
+
+    /// The hand the guide tells us to play, or `None` if the guide's
+    /// character doesn't fit this interpretation.
+    pub fn my_hand(&self, o: HAND, column: char, interpretation: Interpretation) -> Option<HAND> {
+        match interpretation {
+            Interpretation::Shape => self.char_to_hand(column),
+            Interpretation::Outcome => Some(self.find_my_hand(o, self.char_to_game(column)?)),
+        }
+    }
+
+    /// Score of one round, or `None` if the guide's characters don't fit
+    /// this interpretation.
+    pub fn points(
+        &self,
+        opponent: char,
+        column: char,
+        interpretation: Interpretation,
+    ) -> Option<u64> {
+        let o = self.opponent_hand(opponent)?;
+        Some(self.score(o, self.my_hand(o, column, interpretation)?))
+    }
+}
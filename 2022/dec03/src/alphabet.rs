@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+/// The items a rucksack can hold and their priorities. Items are numbered in
+/// the order they are listed, which is also their bit in an `ItemSet`.
+pub struct Alphabet {
+    items: Vec<char>,
+    priorities: Vec<u32>,
+    index: HashMap<char, usize>,
+}
+
+impl Default for Alphabet {
+    /// `a` through `z` have priorities 1 through 26, `A` through `Z` 27 through 52.
+    fn default() -> Alphabet {
+        let mut alphabet = Alphabet {
+            items: vec![],
+            priorities: vec![],
+            index: HashMap::new(),
+        };
+        for (i, c) in ('a'..='z').chain('A'..='Z').enumerate() {
+            alphabet.add(c, i as u32 + 1);
+        }
+        alphabet
+    }
+}
+
+impl Alphabet {
+    /// An `ItemSet` has room for this many different items.
+    pub const MAX_ITEMS: usize = 64;
+
+    fn add(&mut self, c: char, priority: u32) {
+        self.index.insert(c, self.items.len());
+        self.items.push(c);
+        self.priorities.push(priority);
+    }
+
+    /// Reads a mapping with one `<item> <priority>` entry per line, where the
+    /// item is a single character. Blank lines are ignored.
+    pub fn from_mapping(text: &str) -> Result<Alphabet, String> {
+        let mut alphabet = Alphabet {
+            items: vec![],
+            priorities: vec![],
+            index: HashMap::new(),
+        };
+
+        for (idx, line) in text.lines().enumerate() {
+            let line_no = idx + 1;
+            let mut chars = line.chars();
+            let c = match chars.next() {
+                None => continue,
+                Some(c) => c,
+            };
+
+            let priority: u32 = str::parse(chars.as_str().trim()).map_err(|_| {
+                format!(
+                    "line {}: expected an item followed by its priority, got {:?}",
+                    line_no, line
+                )
+            })?;
+            if alphabet.index.contains_key(&c) {
+                return Err(format!("line {}: item {:?} is listed twice", line_no, c));
+            }
+            if alphabet.items.len() == Self::MAX_ITEMS {
+                return Err(format!(
+                    "line {}: more than {} items",
+                    line_no,
+                    Self::MAX_ITEMS
+                ));
+            }
+            alphabet.add(c, priority);
+        }
+
+        Ok(alphabet)
+    }
+
+    pub fn index(&self, c: char) -> Option<usize> {
+        self.index.get(&c).copied()
+    }
+
+    pub fn item(&self, idx: usize) -> char {
+        self.items[idx]
+    }
+
+    /// Widened to u64 so that sums of priorities from a mapping don't
+    /// overflow.
+    pub fn priority(&self, idx: usize) -> u64 {
+        self.priorities[idx] as u64
+    }
+
+    /// The index of every item in a rucksack, rejecting items that aren't
+    /// in the alphabet.
+    pub fn parse(&self, line_no: usize, rucksack: &str) -> Result<Vec<usize>, String> {
+        rucksack
+            .chars()
+            .enumerate()
+            .map(|(col, c)| {
+                self.index(c).ok_or_else(|| {
+                    format!(
+                        "line {}, column {}: {:?} is not an item",
+                        line_no,
+                        col + 1,
+                        c
+                    )
+                })
+            })
+            .collect()
+    }
+}
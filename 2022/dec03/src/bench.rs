@@ -0,0 +1,144 @@
+use std::{collections::HashSet, time::Instant};
+
+use crate::{alphabet::Alphabet, itemset::ItemSet};
+
+struct Rng(u64);
+
+impl Rng {
+    fn below(&mut self, n: u64) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 % n
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+}
+
+const ITEMS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Generates groups of rucksacks where each rucksack has exactly one item in
+/// both compartments and each group shares exactly one badge.
+fn synthetic(lines: usize, group_size: usize, rng: &mut Rng) -> Vec<String> {
+    let items: Vec<char> = ITEMS.chars().collect();
+    let mut out = Vec::with_capacity(lines);
+
+    while out.len() < lines {
+        let badge = items[rng.below(items.len() as u64) as usize];
+        let offset = rng.below(group_size as u64) as usize;
+
+        for member in 0..group_size {
+            // Every other item is left out of one member, so the badge is the
+            // only item the whole group has in common.
+            let mut allowed: Vec<char> = items
+                .iter()
+                .enumerate()
+                .filter(|&(p, &c)| c != badge && (p + offset) % group_size != member)
+                .map(|(_, &c)| c)
+                .collect();
+            rng.shuffle(&mut allowed);
+
+            let m = usize::min(3 + rng.below(10) as usize, (allowed.len() - 2) / 2);
+            let shared = allowed[0];
+            let mut left = vec![badge, shared];
+            left.extend(&allowed[1..1 + m]);
+            let mut right = vec![shared];
+            right.extend(&allowed[1 + m..2 + 2 * m]);
+            rng.shuffle(&mut left);
+            rng.shuffle(&mut right);
+
+            out.push(left.into_iter().chain(right).collect());
+        }
+    }
+
+    out
+}
+
+fn hashset_intersection(mut sets: Vec<HashSet<char>>) -> HashSet<char> {
+    let init = sets.pop().unwrap();
+    sets.into_iter()
+        .fold(init, |a, b| a.intersection(&b).copied().collect())
+}
+
+fn hashset_sums(alphabet: &Alphabet, lines: &[String], group_size: usize) -> (u64, u64) {
+    let priority = |c: char| alphabet.priority(alphabet.index(c).unwrap());
+    let mut compartment_sum = 0;
+    let mut badge_sum = 0;
+
+    for group in lines.chunks(group_size) {
+        for rucksack in group {
+            let items: Vec<char> = rucksack.chars().collect();
+            let (left, right) = items.split_at(items.len() / 2);
+            let shared = hashset_intersection(vec![
+                left.iter().copied().collect(),
+                right.iter().copied().collect(),
+            ]);
+            compartment_sum += priority(*shared.iter().next().unwrap());
+        }
+
+        let sets: Vec<HashSet<char>> = group.iter().map(|x| x.chars().collect()).collect();
+        let badge = hashset_intersection(sets);
+        badge_sum += priority(*badge.iter().next().unwrap());
+    }
+
+    (compartment_sum, badge_sum)
+}
+
+fn itemset_sums(alphabet: &Alphabet, lines: &[String], group_size: usize) -> (u64, u64) {
+    let mut compartment_sum = 0;
+    let mut badge_sum = 0;
+
+    for group in lines.chunks(group_size) {
+        let mut sets = vec![];
+        for rucksack in group {
+            let items = alphabet.parse(0, rucksack).unwrap();
+            let (left, right) = items.split_at(items.len() / 2);
+            let (left, right) = (ItemSet::from_items(left), ItemSet::from_items(right));
+            compartment_sum += alphabet.priority(left.intersection(right).single().unwrap());
+            sets.push(left.union(right));
+        }
+        let badge = sets
+            .into_iter()
+            .reduce(ItemSet::intersection)
+            .and_then(ItemSet::single)
+            .unwrap();
+        badge_sum += alphabet.priority(badge);
+    }
+
+    (compartment_sum, badge_sum)
+}
+
+pub fn run(lines: usize, group_size: usize) {
+    assert!(group_size > 1, "benchmark needs groups of at least 2");
+    let lines = lines - lines % group_size;
+    let input = synthetic(lines, group_size, &mut Rng(2022));
+    let alphabet = Alphabet::default();
+    println!("generated {} rucksacks", input.len());
+
+    let mut answers = vec![];
+    for (name, sums) in [
+        (
+            "hashset",
+            hashset_sums as fn(&Alphabet, &[String], usize) -> (u64, u64),
+        ),
+        ("itemset", itemset_sums),
+    ] {
+        let start = Instant::now();
+        let answer = sums(&alphabet, &input, group_size);
+        let secs = start.elapsed().as_secs_f64();
+        println!(
+            "{}: {:?} in {:.3}s ({:.0} rucksacks/s)",
+            name,
+            answer,
+            secs,
+            input.len() as f64 / secs
+        );
+        answers.push(answer);
+    }
+
+    assert_eq!(answers[0], answers[1], "hashset and itemset paths disagree");
+}
@@ -0,0 +1,172 @@
+use crate::{alphabet::Alphabet, itemset::ItemSet};
+
+fn list(alphabet: &Alphabet, set: ItemSet) -> String {
+    if set.is_empty() {
+        return "none".to_string();
+    }
+    set.items()
+        .map(|i| alphabet.item(i).to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn count(alphabet: &Alphabet, set: ItemSet, what: &str) -> String {
+    match set.len() {
+        0 => format!("no {}", what),
+        n => format!("{} {}: {}", n, what, list(alphabet, set)),
+    }
+}
+
+/// A line with a `^` under every item of `rucksack` that is in `set`.
+fn markers(alphabet: &Alphabet, rucksack: &str, set: ItemSet) -> String {
+    rucksack
+        .chars()
+        .map(|c| match alphabet.index(c) {
+            Some(i) if set.contains(i) => '^',
+            _ => ' ',
+        })
+        .collect::<String>()
+        .trim_end()
+        .to_string()
+}
+
+/// Collects problems in hand-edited inputs instead of stopping at the first.
+pub struct Diagnostics<'a> {
+    alphabet: &'a Alphabet,
+    rucksacks: usize,
+    bad_rucksacks: usize,
+    groups: usize,
+    bad_groups: usize,
+    unreadable: usize,
+    skipped: usize,
+    incomplete: bool,
+}
+
+impl<'a> Diagnostics<'a> {
+    pub fn new(alphabet: &'a Alphabet) -> Diagnostics<'a> {
+        Diagnostics {
+            alphabet,
+            rucksacks: 0,
+            bad_rucksacks: 0,
+            groups: 0,
+            bad_groups: 0,
+            unreadable: 0,
+            skipped: 0,
+            incomplete: false,
+        }
+    }
+
+    /// Reports the rucksack if there isn't exactly one item in both
+    /// compartments.
+    pub fn rucksack(&mut self, line_no: usize, rucksack: &str, shared: ItemSet) {
+        self.rucksacks += 1;
+        if shared.single().is_some() {
+            return;
+        }
+
+        self.bad_rucksacks += 1;
+        println!(
+            "line {}: {}",
+            line_no,
+            count(self.alphabet, shared, "items in both compartments")
+        );
+        println!("  {}", rucksack);
+        if !shared.is_empty() {
+            println!("  {}", markers(self.alphabet, rucksack, shared));
+        }
+    }
+
+    /// Reports a rucksack that can't be split into compartments of known
+    /// items. `reason` already names the line.
+    pub fn unreadable(&mut self, reason: &str) {
+        self.rucksacks += 1;
+        self.bad_rucksacks += 1;
+        self.unreadable += 1;
+        println!("{}", reason);
+    }
+
+    /// Reports a group that isn't checked because one of its rucksacks
+    /// can't be read.
+    pub fn skip(&mut self, first_line: usize, last_line: usize) {
+        self.groups += 1;
+        self.bad_groups += 1;
+        self.skipped += 1;
+        println!(
+            "lines {}-{}: skipped, a rucksack can't be read",
+            first_line, last_line
+        );
+    }
+
+    /// Reports the group with every candidate badge if there isn't exactly
+    /// one. A group without candidates lists, for every rucksack, the items
+    /// all the others share.
+    pub fn group(&mut self, first_line: usize, group: &[(String, ItemSet)]) {
+        self.groups += 1;
+        let sets: Vec<ItemSet> = group.iter().map(|(_, set)| *set).collect();
+        let badges = sets.iter().copied().reduce(ItemSet::intersection).unwrap();
+        if badges.single().is_some() {
+            return;
+        }
+
+        self.bad_groups += 1;
+        println!(
+            "lines {}-{}: {}",
+            first_line,
+            first_line + group.len() - 1,
+            count(self.alphabet, badges, "badge candidates")
+        );
+        for (i, (rucksack, set)) in group.iter().enumerate() {
+            println!("  line {}: {}", first_line + i, rucksack);
+            if !badges.is_empty() {
+                println!(
+                    "  {:width$} {}",
+                    "",
+                    markers(self.alphabet, rucksack, badges),
+                    width = format!("line {}:", first_line + i).len()
+                );
+                continue;
+            }
+
+            let others = sets
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, s)| *s)
+                .reduce(ItemSet::intersection)
+                .unwrap_or_default();
+            println!(
+                "    missing items every other rucksack has: {}",
+                list(self.alphabet, others.difference(*set))
+            );
+        }
+    }
+
+    pub fn incomplete(&mut self) {
+        self.incomplete = true;
+    }
+
+    /// Prints the summary and returns whether any problems were found.
+    pub fn summary(&self) -> bool {
+        println!(
+            "{} of {} rucksacks and {} of {} groups need attention{}{}",
+            self.bad_rucksacks,
+            self.rucksacks,
+            self.bad_groups,
+            self.groups,
+            if self.unreadable > 0 {
+                format!(
+                    " ({} rucksacks can't be read, {} groups skipped)",
+                    self.unreadable, self.skipped
+                )
+            } else {
+                String::new()
+            },
+            if self.incomplete {
+                ", and the last group is incomplete"
+            } else {
+                ""
+            }
+        );
+        self.bad_rucksacks > 0 || self.bad_groups > 0 || self.incomplete
+    }
+}
@@ -0,0 +1,51 @@
+/// A set of items stored as a bitmask, where bit `i` is set when the item
+/// with index `i` in the `Alphabet` is present.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub fn from_items(items: &[usize]) -> ItemSet {
+        let mut set = ItemSet::default();
+        for &i in items {
+            set.insert(i);
+        }
+        set
+    }
+
+    pub fn insert(&mut self, i: usize) {
+        self.0 |= 1 << i;
+    }
+
+    pub fn contains(self, i: usize) -> bool {
+        self.0 & (1 << i) != 0
+    }
+
+    pub fn intersection(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+
+    pub fn union(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 | other.0)
+    }
+
+    pub fn difference(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & !other.0)
+    }
+
+    pub fn len(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn items(self) -> impl Iterator<Item = usize> {
+        (0..64).filter(move |&i| self.contains(i))
+    }
+
+    /// The index of the only item in the set, if it has exactly one.
+    pub fn single(self) -> Option<usize> {
+        (self.len() == 1).then(|| self.0.trailing_zeros() as usize)
+    }
+}
@@ -1,38 +1,225 @@
-use std::{collections::HashSet, io::stdin};
+mod alphabet;
+mod bench;
+mod diagnose;
+mod itemset;
+mod unshuffle;
 
-fn item_priority(c: char) -> u32 {
-    if ('a'..='z').contains(&c) {
-        c as u32 - 'a' as u32 + 1
-    } else {
-        c as u32 - 'A' as u32 + 1 + 26
+use std::{
+    env::args,
+    fs,
+    io::{self, stdin},
+    process::exit,
+};
+
+use alphabet::Alphabet;
+use diagnose::Diagnostics;
+use itemset::ItemSet;
+
+enum Mode {
+    Sum,
+    Diagnose,
+    Unshuffle,
+    Bench(usize),
+}
+
+struct Options {
+    mode: Mode,
+    group_size: usize,
+    alphabet: Alphabet,
+}
+
+/// `dec03 [diagnose | unshuffle | bench [LINES]] [--group-size K] [--alphabet FILE]`
+fn parse_args() -> Options {
+    let mut options = Options {
+        mode: Mode::Sum,
+        group_size: 3,
+        alphabet: Alphabet::default(),
+    };
+    let mut args = args().skip(1).peekable();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "diagnose" => options.mode = Mode::Diagnose,
+            "unshuffle" => options.mode = Mode::Unshuffle,
+            "bench" => {
+                let lines = args
+                    .next_if(|a| !a.starts_with("--"))
+                    .map(|n| str::parse(&n).expect("bench needs a number of lines"));
+                options.mode = Mode::Bench(lines.unwrap_or(3_000_000))
+            }
+            "--group-size" => {
+                options.group_size = args
+                    .next()
+                    .and_then(|k| str::parse(&k).ok())
+                    .filter(|&k| k > 0)
+                    .expect("--group-size needs a positive integer")
+            }
+            "--alphabet" => {
+                let path = args.next().expect("--alphabet needs a mapping file");
+                let text = fs::read_to_string(&path)
+                    .unwrap_or_else(|e| panic!("can't read {}: {}", path, e));
+                options.alphabet = Alphabet::from_mapping(&text).unwrap_or_else(|e| {
+                    eprintln!("{}: {}", path, e);
+                    exit(1);
+                });
+            }
+            _ => panic!("unknown argument {:?}", arg),
+        }
+    }
+
+    options
+}
+
+/// The text of an input line. Lines that aren't valid UTF-8 and read errors
+/// stop the program like any other bad line.
+fn text(line_no: usize, line: io::Result<String>) -> String {
+    line.unwrap_or_else(|e| {
+        eprintln!("line {}: {}", line_no, e);
+        exit(1);
+    })
+}
+
+/// Splits a rucksack into the item sets of its two compartments.
+fn compartments(
+    alphabet: &Alphabet,
+    line_no: usize,
+    rucksack: &str,
+) -> Result<(ItemSet, ItemSet), String> {
+    let items = alphabet.parse(line_no, rucksack)?;
+    if !items.len().is_multiple_of(2) {
+        return Err(format!(
+            "line {}: {} items can't be split into two compartments",
+            line_no,
+            items.len()
+        ));
     }
+
+    let (left, right) = items.split_at(items.len() / 2);
+    Ok((ItemSet::from_items(left), ItemSet::from_items(right)))
 }
 
 fn main() {
-    let mut sum = 0;
-
-    let mut lines = stdin().lines();
-
-    while let Some(Ok(line)) = lines.next() {
-        let group = vec![
-            line,
-            lines.next().unwrap().unwrap(),
-            lines.next().unwrap().unwrap(),
-        ];
-        let mut sets: Vec<HashSet<char>> = group
-            .into_iter()
-            .map(|x| x.chars().into_iter().collect())
+    let options = parse_args();
+    if let Mode::Bench(lines) = options.mode {
+        bench::run(lines, options.group_size);
+        return;
+    }
+
+    if let Mode::Unshuffle = options.mode {
+        let sets: Vec<ItemSet> = stdin()
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                let line = text(i + 1, line);
+                let (left, right) =
+                    compartments(&options.alphabet, i + 1, &line).unwrap_or_else(|e| {
+                        eprintln!("{}", e);
+                        exit(1);
+                    });
+                left.union(right)
+            })
             .collect();
-        let init = sets.pop().unwrap();
-        let fset: HashSet<char> = sets.into_iter().fold(init, |a, b| {
-            a.intersection(&b).into_iter().copied().collect()
-        });
-        if fset.len() != 1 {
-            panic!("expected exactly 1 badge item")
+        if !unshuffle::print(&options.alphabet, &sets, options.group_size) {
+            exit(1);
+        }
+        return;
+    }
+
+    let diagnose = matches!(options.mode, Mode::Diagnose);
+    let mut diagnostics = Diagnostics::new(&options.alphabet);
+    let mut compartment_sum = 0;
+    let mut badge_sum = 0;
+    let mut group = vec![];
+    // Whether the current group has a rucksack that can't be read.
+    let mut unreadable = false;
+    let mut line_no = 0;
+
+    for line in stdin().lines() {
+        line_no += 1;
+        let line = text(line_no, line);
+        let set = match compartments(&options.alphabet, line_no, &line) {
+            Ok((left, right)) => {
+                let shared = left.intersection(right);
+                if diagnose {
+                    diagnostics.rucksack(line_no, &line, shared);
+                } else {
+                    match shared.single() {
+                        Some(i) => compartment_sum += options.alphabet.priority(i),
+                        None => {
+                            eprintln!(
+                                "line {}: expected exactly 1 item in both compartments, found {}",
+                                line_no,
+                                shared.len()
+                            );
+                            exit(1);
+                        }
+                    }
+                }
+                left.union(right)
+            }
+            Err(e) if diagnose => {
+                diagnostics.unreadable(&e);
+                unreadable = true;
+                ItemSet::default()
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                exit(1);
+            }
+        };
+
+        group.push((line, set));
+        if group.len() == options.group_size {
+            let first_line = line_no + 1 - group.len();
+            if unreadable {
+                diagnostics.skip(first_line, line_no);
+            } else if diagnose {
+                diagnostics.group(first_line, &group);
+            } else {
+                let badges = group
+                    .iter()
+                    .map(|(_, set)| *set)
+                    .reduce(ItemSet::intersection)
+                    .unwrap();
+                match badges.single() {
+                    Some(i) => badge_sum += options.alphabet.priority(i),
+                    None => {
+                        eprintln!(
+                            "lines {}-{}: expected exactly 1 badge item, found {}",
+                            first_line,
+                            line_no,
+                            badges.len()
+                        );
+                        exit(1);
+                    }
+                }
+            }
+            group.clear();
+            unreadable = false;
+        }
+    }
+
+    if !group.is_empty() {
+        eprintln!(
+            "lines {}-{}: incomplete group of {} rucksacks, expected {}",
+            line_no - group.len() + 1,
+            line_no,
+            group.len(),
+            options.group_size
+        );
+        if !diagnose {
+            exit(1);
+        }
+        diagnostics.incomplete();
+    }
+
+    if diagnose {
+        if diagnostics.summary() {
+            exit(1);
         }
-        let badge = fset.iter().next().unwrap();
-        sum += item_priority(*badge);
+        return;
     }
 
-    println!("{}", sum)
+    println!("{}", compartment_sum);
+    println!("{}", badge_sum)
 }
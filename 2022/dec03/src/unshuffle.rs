@@ -0,0 +1,267 @@
+use crate::{alphabet::Alphabet, itemset::ItemSet};
+
+/// Rucksacks with at least this many ways to complete their group are all
+/// equally good to branch on.
+const COUNT_LIMIT: usize = 64;
+
+/// Exact cover search that grows groups as it goes: it always completes the
+/// uncovered rucksack with the fewest ways left to complete its group, and
+/// only adds a rucksack to a group while the group still has an item in
+/// common. Nothing is precomputed, so memory only grows with the depth.
+struct Search<'a> {
+    sets: &'a [ItemSet],
+    group_size: usize,
+    covered: Vec<bool>,
+    chosen: Vec<Vec<usize>>,
+}
+
+impl Search<'_> {
+    fn partners(&self, r: usize) -> Vec<usize> {
+        (0..self.sets.len())
+            .filter(|&s| {
+                s != r && !self.covered[s] && !self.sets[r].intersection(self.sets[s]).is_empty()
+            })
+            .collect()
+    }
+
+    /// Number of ways to complete a group from `partners[from..]`, counting
+    /// no further than `cap`.
+    fn count(
+        &self,
+        partners: &[usize],
+        from: usize,
+        missing: usize,
+        shared: ItemSet,
+        cap: usize,
+    ) -> usize {
+        if missing == 0 {
+            return usize::from(shared.single().is_some());
+        }
+
+        let mut n = 0;
+        for i in from..(partners.len() + 1).saturating_sub(missing) {
+            let next = shared.intersection(self.sets[partners[i]]);
+            if next.is_empty() {
+                continue;
+            }
+            n += self.count(partners, i + 1, missing - 1, next, cap - n);
+            if n >= cap {
+                break;
+            }
+        }
+        n
+    }
+
+    fn solve(&mut self) -> bool {
+        let mut next = None;
+        let mut fewest = COUNT_LIMIT;
+        for r in (0..self.sets.len()).filter(|&r| !self.covered[r]) {
+            let partners = self.partners(r);
+            let n = self.count(&partners, 0, self.group_size - 1, self.sets[r], fewest);
+            if n == 0 {
+                return false;
+            }
+            if n < fewest || next.is_none() {
+                fewest = n;
+                next = Some((r, partners));
+            }
+        }
+        let (r, partners) = match next {
+            None => return true,
+            Some(next) => next,
+        };
+
+        self.covered[r] = true;
+        let found = self.grow(&partners, 0, &mut vec![r], self.sets[r]);
+        if !found {
+            self.covered[r] = false;
+        }
+        found
+    }
+
+    /// Tries every way to complete `group` from `partners[from..]`, keeping
+    /// the members of `group` covered while searching the rest.
+    fn grow(
+        &mut self,
+        partners: &[usize],
+        from: usize,
+        group: &mut Vec<usize>,
+        shared: ItemSet,
+    ) -> bool {
+        let missing = self.group_size - group.len();
+        if missing == 0 {
+            if shared.single().is_none() {
+                return false;
+            }
+            self.chosen.push(group.clone());
+            if self.solve() {
+                return true;
+            }
+            self.chosen.pop();
+            return false;
+        }
+
+        for i in from..(partners.len() + 1).saturating_sub(missing) {
+            let s = partners[i];
+            let next = shared.intersection(self.sets[s]);
+            if next.is_empty() {
+                continue;
+            }
+            group.push(s);
+            self.covered[s] = true;
+            if self.grow(partners, i + 1, group, next) {
+                return true;
+            }
+            self.covered[s] = false;
+            group.pop();
+        }
+        false
+    }
+}
+
+/// Finds groups of `group_size` rucksacks in any order that each share
+/// exactly one badge, or `None` if no such partition exists.
+pub fn partition(sets: &[ItemSet], group_size: usize) -> Option<Vec<Vec<usize>>> {
+    if !sets.len().is_multiple_of(group_size) {
+        return None;
+    }
+
+    let mut search = Search {
+        sets,
+        group_size,
+        covered: vec![false; sets.len()],
+        chosen: vec![],
+    };
+    if !search.solve() {
+        return None;
+    }
+    Some(search.chosen)
+}
+
+/// Prints the groups of one partition and their badge sum. Several
+/// partitions may exist and they can have different badges, so the sum isn't
+/// unique: the puzzle input in its given order sums to 2758, but the
+/// partition found here sums to 2657.
+pub fn print(alphabet: &Alphabet, sets: &[ItemSet], group_size: usize) -> bool {
+    let mut groups = match partition(sets, group_size) {
+        Some(groups) => groups,
+        None => {
+            println!(
+                "no partition of {} rucksacks into groups of {} with exactly one badge each",
+                sets.len(),
+                group_size
+            );
+            return false;
+        }
+    };
+    groups.sort();
+
+    let mut sum = 0;
+    for (n, group) in groups.iter().enumerate() {
+        let badge = group
+            .iter()
+            .map(|&i| sets[i])
+            .reduce(ItemSet::intersection)
+            .and_then(ItemSet::single)
+            .unwrap();
+        sum += alphabet.priority(badge);
+
+        let lines: Vec<String> = group.iter().map(|i| (i + 1).to_string()).collect();
+        println!(
+            "group {}: lines {} share {}",
+            n + 1,
+            lines.join(", "),
+            alphabet.item(badge)
+        );
+    }
+    println!("{}", sum);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sets(rucksacks: &[&str]) -> Vec<ItemSet> {
+        let alphabet = Alphabet::default();
+        rucksacks
+            .iter()
+            .map(|r| ItemSet::from_items(&alphabet.parse(0, r).unwrap()))
+            .collect()
+    }
+
+    /// Every rucksack is in exactly one group of `group_size`, and every
+    /// group shares exactly one item.
+    fn assert_valid(sets: &[ItemSet], group_size: usize, groups: &[Vec<usize>]) {
+        let mut seen = vec![0; sets.len()];
+        for group in groups {
+            assert_eq!(group.len(), group_size, "group {:?}", group);
+            let shared = group
+                .iter()
+                .map(|&i| sets[i])
+                .reduce(ItemSet::intersection)
+                .unwrap();
+            assert_eq!(shared.len(), 1, "group {:?} shares {:?}", group, shared);
+            for &i in group {
+                seen[i] += 1;
+            }
+        }
+        assert!(seen.iter().all(|&n| n == 1), "rucksacks used {:?}", seen);
+    }
+
+    #[test]
+    fn finds_the_only_partition_of_a_shuffled_instance() {
+        // Groups `a`, `b` and `c` in shuffled order. `abq`, `bt` and `bu`
+        // also share one item, and so do `bvc`, `cw` and `cx`, but neither
+        // group leaves a partition of the rest.
+        let sets = sets(&["cx", "abq", "bu", "as", "cw", "bvc", "ar", "bt", "cy"]);
+        let mut groups = partition(&sets, 3).unwrap();
+        assert_valid(&sets, 3, &groups);
+        groups.iter_mut().for_each(|g| g.sort());
+        groups.sort();
+        assert_eq!(groups, [vec![0, 4, 8], vec![1, 3, 6], vec![2, 5, 7]]);
+    }
+
+    #[test]
+    fn finds_a_partition_of_the_shuffled_example() {
+        // The puzzle example with its two groups interleaved, which can
+        // also be grouped differently than in order.
+        let sets = sets(&[
+            "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn",
+            "vJrwpWtwJgWrhcsFMMfFFhFp",
+            "ttgJtRGJQctTZtZT",
+            "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+            "CrZsJsPPZsGzwwsLwLmpwMDw",
+            "PmmdzqPrVvPwwTWBwg",
+        ]);
+        assert_valid(&sets, 3, &partition(&sets, 3).unwrap());
+    }
+
+    #[test]
+    fn finds_a_partition_of_the_shuffled_input() {
+        let input: Vec<&str> = include_str!("../input").lines().collect();
+        // 7 is coprime to the 300 lines, so this visits every line once.
+        let shuffled: Vec<&str> = (0..input.len())
+            .map(|i| input[i * 7 % input.len()])
+            .collect();
+        let sets = sets(&shuffled);
+        assert_valid(&sets, 3, &partition(&sets, 3).unwrap());
+    }
+
+    #[test]
+    fn rejects_inputs_without_a_partition() {
+        // No items in common.
+        assert_eq!(partition(&sets(&["ab", "cd", "ef"]), 3), None);
+        // Two badge candidates.
+        assert_eq!(partition(&sets(&["ab", "ab", "ab"]), 3), None);
+        // Every rucksack shares an item with `abc`, but only one can pair
+        // with it.
+        assert_eq!(partition(&sets(&["abc", "a", "b", "c"]), 2), None);
+    }
+
+    #[test]
+    fn rejects_lengths_that_are_not_a_multiple_of_the_group_size() {
+        assert_eq!(partition(&sets(&["ab", "ab", "ab", "ab"]), 3), None);
+        assert_eq!(partition(&sets(&["ab", "bc", "ca"]), 2), None);
+    }
+}
@@ -0,0 +1,97 @@
+use std::{fmt, io::BufRead, str::FromStr};
+
+use crate::range::Range;
+
+#[derive(Debug)]
+pub struct ParseError {
+    pub line: usize,
+    pub text: String,
+    pub reason: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}: {:?}", self.line, self.reason, self.text)
+    }
+}
+
+/// The elves assigned together on one line of the input.
+pub struct Line<T> {
+    pub line: usize,
+    pub elves: Vec<Range<T>>,
+}
+
+impl<T: fmt::Display> fmt::Display for Line<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let elves: Vec<String> = self.elves.iter().map(Range::to_string).collect();
+        write!(f, "{}", elves.join(","))
+    }
+}
+
+impl<T: Ord> Line<T> {
+    /// Every pair of elves on the line, each pair once.
+    pub fn pairs(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let n = self.elves.len();
+        (0..n).flat_map(move |i| (i + 1..n).map(move |j| (i, j)))
+    }
+
+    /// For every elf whose sections are all covered by another elf on the
+    /// line, that elf's index and the index of one that covers it. Of elves
+    /// with equal ranges only the first is kept.
+    pub fn redundant(&self) -> Vec<(usize, usize)> {
+        let mut out = vec![];
+        for (j, elf) in self.elves.iter().enumerate() {
+            let cover = self
+                .elves
+                .iter()
+                .enumerate()
+                .find(|&(i, other)| i != j && other.contains(elf) && (other != elf || i < j));
+            if let Some((i, _)) = cover {
+                out.push((j, i));
+            }
+        }
+        out
+    }
+}
+
+/// Reads one line per group of elves, each a comma-separated list of at
+/// least two `START-END` assignments. Empty lines are skipped.
+pub fn parse<T, R>(input: R) -> Result<Vec<Line<T>>, ParseError>
+where
+    T: Ord + FromStr,
+    R: BufRead,
+{
+    let mut lines = vec![];
+
+    for (idx, line) in input.lines().enumerate() {
+        let error = |text: &str, reason: &str| ParseError {
+            line: idx + 1,
+            text: text.to_string(),
+            reason: reason.to_string(),
+        };
+
+        let line = line.map_err(|e| error("", &e.to_string()))?;
+        let text = line.trim_end();
+        if text.is_empty() {
+            continue;
+        }
+
+        let elves: Vec<Range<T>> = text
+            .split(',')
+            .enumerate()
+            .map(|(n, fromto)| {
+                str::parse(fromto.trim())
+                    .map_err(|reason| error(text, &format!("elf {}: {}", n + 1, reason)))
+            })
+            .collect::<Result<_, _>>()?;
+        if elves.len() < 2 {
+            return Err(error(text, "expected at least two assignments"));
+        }
+        lines.push(Line {
+            line: idx + 1,
+            elves,
+        });
+    }
+
+    Ok(lines)
+}
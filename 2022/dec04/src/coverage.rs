@@ -0,0 +1,135 @@
+use crate::range::Range;
+
+/// Sorted, non-overlapping and non-adjacent runs of covered sections.
+pub struct RangeSet {
+    runs: Vec<Range<u32>>,
+}
+
+impl RangeSet {
+    pub fn from_ranges(ranges: &[Range<u32>]) -> RangeSet {
+        let mut sorted = ranges.to_vec();
+        sorted.sort_by_key(|r| (r.start, r.end));
+
+        let mut runs: Vec<Range<u32>> = vec![];
+        for range in sorted {
+            match runs.last_mut() {
+                Some(last) if range.start as u64 <= last.end as u64 + 1 => {
+                    last.end = last.end.max(range.end)
+                }
+                _ => runs.push(range),
+            }
+        }
+        RangeSet { runs }
+    }
+
+    pub fn runs(&self) -> &[Range<u32>] {
+        &self.runs
+    }
+
+    /// Number of sections covered by at least one range.
+    pub fn sections(&self) -> u64 {
+        self.runs.iter().map(Range::len).sum()
+    }
+
+    /// The uncovered sections between consecutive runs.
+    pub fn gaps(&self) -> Vec<Range<u32>> {
+        self.runs
+            .windows(2)
+            .map(|w| Range {
+                start: w[0].end + 1,
+                end: w[1].start - 1,
+            })
+            .collect()
+    }
+}
+
+/// Splits the covered sections into pieces covered by the same number of
+/// ranges, in order, by sweeping over where ranges start and end.
+fn depths(ranges: &[Range<u32>]) -> Vec<(Range<u32>, usize)> {
+    let mut events: Vec<(u64, isize)> = vec![];
+    for r in ranges {
+        events.push((r.start as u64, 1));
+        events.push((r.end as u64 + 1, -1));
+    }
+    events.sort_unstable();
+
+    let mut pieces = vec![];
+    let mut depth = 0;
+    let mut i = 0;
+    while i < events.len() {
+        let at = events[i].0;
+        while i < events.len() && events[i].0 == at {
+            depth += events[i].1;
+            i += 1;
+        }
+        if depth > 0 {
+            let next = events[i].0;
+            pieces.push((
+                Range {
+                    start: at as u32,
+                    end: (next - 1) as u32,
+                },
+                depth as usize,
+            ));
+        }
+    }
+    pieces
+}
+
+/// A range, or a single section on its own.
+fn show(range: &Range<u32>) -> String {
+    if range.start == range.end {
+        range.start.to_string()
+    } else {
+        format!("{}-{}", range.start, range.end)
+    }
+}
+
+pub fn print(ranges: &[Range<u32>]) {
+    let set = RangeSet::from_ranges(ranges);
+    let runs: Vec<String> = set.runs().iter().map(show).collect();
+    println!(
+        "{} sections covered in {} runs: {}",
+        set.sections(),
+        runs.len(),
+        runs.join(", ")
+    );
+
+    let gaps = set.gaps();
+    if gaps.is_empty() {
+        println!("no gaps");
+    } else {
+        let sections: u64 = gaps.iter().map(Range::len).sum();
+        let gaps: Vec<String> = gaps.iter().map(show).collect();
+        println!(
+            "{} sections uncovered in {} gaps: {}",
+            sections,
+            gaps.len(),
+            gaps.join(", ")
+        );
+    }
+
+    let pieces = depths(ranges);
+    let max = match pieces.iter().map(|&(_, d)| d).max() {
+        None => return,
+        Some(max) => max,
+    };
+    let busiest: Vec<String> = pieces
+        .iter()
+        .filter(|&&(_, d)| d == max)
+        .map(|(r, _)| show(r))
+        .collect();
+    println!("most covered: {} by {} elves", busiest.join(", "), max);
+
+    let mut histogram = vec![0; max + 1];
+    for (range, depth) in &pieces {
+        histogram[*depth] += range.len();
+    }
+    println!("elves  sections");
+    for (depth, &sections) in histogram.iter().enumerate() {
+        if sections == 0 {
+            continue;
+        }
+        println!("{:5}  {}", depth, sections);
+    }
+}
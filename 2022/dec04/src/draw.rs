@@ -0,0 +1,137 @@
+use crate::{assignments::Line, range::Range};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// Overlapping sections are marked with `^` on a line of their own.
+    Plain,
+    /// Overlapping sections are drawn in bold red.
+    Ansi,
+}
+
+/// Maps sections to columns, with `scale` sections per column when the
+/// assignments are wider than the terminal. Bounds are kept in u64 so that
+/// ranges reaching `u32::MAX` don't overflow.
+struct Axis {
+    lo: u64,
+    hi: u64,
+    scale: u64,
+}
+
+impl Axis {
+    fn new(ranges: &[Range<u32>], width: u32) -> Axis {
+        let lo = ranges.iter().map(|r| r.start).min().unwrap_or(1).min(1) as u64;
+        let hi = ranges.iter().map(|r| r.end).max().unwrap_or(1) as u64;
+        let span = hi - lo + 1;
+        Axis {
+            lo,
+            hi,
+            scale: span.div_ceil(width.max(1) as u64),
+        }
+    }
+
+    fn columns(&self) -> u64 {
+        (self.hi - self.lo + 1).div_ceil(self.scale)
+    }
+
+    fn column(&self, col: u64) -> Range<u64> {
+        let start = self.lo + col * self.scale;
+        Range {
+            start,
+            end: self.hi.min(start + self.scale - 1),
+        }
+    }
+
+    /// The digit of a section, as in the puzzle, or `#` for a column that
+    /// stands for several sections.
+    fn mark(&self, col: u64) -> char {
+        if self.scale == 1 {
+            char::from_digit((self.column(col).start % 10) as u32, 10).unwrap()
+        } else {
+            '#'
+        }
+    }
+}
+
+fn wide(range: &Range<u32>) -> Range<u64> {
+    Range {
+        start: range.start as u64,
+        end: range.end as u64,
+    }
+}
+
+fn intersection(a: &Range<u32>, b: &Range<u32>) -> Option<Range<u32>> {
+    a.has_overlap(b).then(|| Range {
+        start: a.start.max(b.start),
+        end: a.end.min(b.end),
+    })
+}
+
+fn strip(axis: &Axis, range: &Range<u32>, shared: &[Range<u32>], style: Style) -> String {
+    let mut out = String::new();
+    for col in 0..axis.columns() {
+        let sections = axis.column(col);
+        if !wide(range).has_overlap(&sections) {
+            out.push('.');
+        } else if style == Style::Ansi && shared.iter().any(|s| wide(s).has_overlap(&sections)) {
+            out.push_str(&format!("\x1b[1;31m{}\x1b[0m", axis.mark(col)));
+        } else {
+            out.push(axis.mark(col));
+        }
+    }
+    out
+}
+
+fn markers(axis: &Axis, shared: &[Range<u32>]) -> String {
+    (0..axis.columns())
+        .map(|col| {
+            if shared
+                .iter()
+                .any(|s| wide(s).has_overlap(&axis.column(col)))
+            {
+                '^'
+            } else {
+                ' '
+            }
+        })
+        .collect::<String>()
+        .trim_end()
+        .to_string()
+}
+
+/// Draws the assignments of every line as dotted strips on a shared axis
+/// of at most `width` columns, highlighting sections assigned to more than
+/// one elf on the line. A compressed column is drawn when any of its
+/// sections is assigned, so only the highlighting tells real overlaps apart.
+pub fn print(lines: &[Line<u32>], style: Style, width: u32) {
+    let ranges: Vec<Range<u32>> = lines.iter().flat_map(|l| l.elves.clone()).collect();
+    let axis = Axis::new(&ranges, width);
+    if axis.scale > 1 {
+        println!(
+            "sections {}-{}, {} per column",
+            axis.lo, axis.hi, axis.scale
+        );
+    }
+
+    for line in lines {
+        let elves = &line.elves;
+        let shared: Vec<Range<u32>> = line
+            .pairs()
+            .filter_map(|(i, j)| intersection(&elves[i], &elves[j]))
+            .collect();
+        match &elves[..] {
+            [left, right] => println!(
+                "line {}: {} {}",
+                line.line,
+                line,
+                left.relation(right).name()
+            ),
+            _ => println!("line {}: {}", line.line, line),
+        }
+        for range in elves {
+            println!("  {}  {}", strip(&axis, range, &shared, style), range);
+        }
+        if style == Style::Plain && !shared.is_empty() {
+            println!("  {}", markers(&axis, &shared));
+        }
+    }
+}
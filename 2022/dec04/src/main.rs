@@ -1,37 +1,171 @@
-use std::io::stdin;
+mod assignments;
+mod coverage;
+mod draw;
+mod plan;
+mod range;
+mod sweep;
 
-struct Range {
-    start: u32,
-    end: u32,
+use std::{
+    env::args,
+    io::{stdin, stdout, IsTerminal},
+    process::exit,
+};
+
+use assignments::Line;
+use draw::Style;
+use range::{Range, Relation};
+
+#[derive(PartialEq, Eq)]
+enum Mode {
+    Count,
+    Classify,
+    Coverage,
+    Redundant,
+    Plan { weighted: bool },
+    Pairs,
+    Draw(Style, u32),
 }
 
-impl Range {
-    fn contains(&self, other: &Range) -> bool {
-        self.start <= other.start && other.end <= self.end
+/// `draw [--plain | --color] [--width N]`, in colour when writing to a
+/// terminal.
+fn parse_draw(flags: &[&str]) -> Mode {
+    let mut style = if stdout().is_terminal() {
+        Style::Ansi
+    } else {
+        Style::Plain
+    };
+    let mut width = 80;
+
+    let mut flags = flags.iter();
+    while let Some(&flag) = flags.next() {
+        match flag {
+            "--plain" => style = Style::Plain,
+            "--color" => style = Style::Ansi,
+            "--width" => {
+                width = flags
+                    .next()
+                    .and_then(|w| str::parse(w).ok())
+                    .filter(|&w| w > 0)
+                    .expect("--width needs a positive integer")
+            }
+            _ => panic!("unknown argument {:?}", flag),
+        }
     }
-    fn has_overlap(&self, other: &Range) -> bool {
-        !(self.end < other.start || other.end < self.start)
+
+    Mode::Draw(style, width)
+}
+
+/// `dec04 [classify | redundant | plan [weighted] | coverage | pairs | draw ...]`
+fn parse_args() -> Mode {
+    let args: Vec<String> = args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match args[..] {
+        [] => Mode::Count,
+        ["classify"] => Mode::Classify,
+        ["redundant"] => Mode::Redundant,
+        ["plan"] => Mode::Plan { weighted: false },
+        ["plan", "weighted"] => Mode::Plan { weighted: true },
+        ["coverage"] => Mode::Coverage,
+        ["pairs"] => Mode::Pairs,
+        ["draw", ref flags @ ..] => parse_draw(flags),
+        _ => panic!("unknown arguments {:?}", args),
     }
 }
 
-fn build_range(fromto: &str) -> Range {
-    let mut ns = fromto.split('-');
-    let start = str::parse(ns.next().unwrap()).unwrap();
-    let end = str::parse(ns.next().unwrap()).unwrap();
-    Range { start, end }
+/// Lines where one elf fully contains another, and lines where any two
+/// elves overlap.
+fn count(lines: &[Line<u32>]) {
+    let mut contained = 0;
+    let mut overlapping = 0;
+
+    for line in lines {
+        let elves = &line.elves;
+        if line
+            .pairs()
+            .any(|(i, j)| elves[i].contains(&elves[j]) || elves[j].contains(&elves[i]))
+        {
+            contained += 1;
+        }
+        if line.pairs().any(|(i, j)| elves[i].has_overlap(&elves[j])) {
+            overlapping += 1;
+        }
+    }
+
+    println!("{}", contained);
+    println!("{}", overlapping)
 }
 
-fn main() {
-    let mut count = 0;
+/// Classifies every pair of elves on a line, with a breakdown over all pairs.
+fn classify(lines: &[Line<u32>]) {
+    let mut breakdown = [0; Relation::ALL.len()];
+
+    for line in lines {
+        let elves = &line.elves;
+        if let [left, right] = &elves[..] {
+            let relation = left.relation(right);
+            breakdown[relation as usize] += 1;
+            println!("line {}: {} {}", line.line, line, relation.name());
+            continue;
+        }
 
-    for line in stdin().lines().flatten() {
-        let pair: Vec<&str> = line.split(',').collect();
-        let left = build_range(pair[0]);
-        let right = build_range(pair[1]);
-        if left.has_overlap(&right) {
-            count += 1;
+        println!("line {}: {}", line.line, line);
+        for (i, j) in line.pairs() {
+            let relation = elves[i].relation(&elves[j]);
+            breakdown[relation as usize] += 1;
+            println!("  elves {} and {}: {}", i + 1, j + 1, relation.name());
         }
     }
 
-    println!("{}", count)
+    for relation in Relation::ALL {
+        println!("{}: {}", relation.name(), breakdown[relation as usize]);
+    }
+}
+
+fn redundant(lines: &[Line<u32>]) {
+    let mut elves = 0;
+    let mut affected = 0;
+
+    for line in lines {
+        let redundant = line.redundant();
+        if redundant.is_empty() {
+            continue;
+        }
+        affected += 1;
+        elves += redundant.len();
+        for (j, i) in redundant {
+            println!(
+                "line {}: elf {} ({}) is covered by elf {} ({})",
+                line.line,
+                j + 1,
+                line.elves[j],
+                i + 1,
+                line.elves[i]
+            );
+        }
+    }
+
+    println!("{} redundant elves on {} lines", elves, affected);
+}
+
+fn main() {
+    let mode = parse_args();
+    let lines: Vec<Line<u32>> = assignments::parse(stdin().lock()).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        exit(1);
+    });
+    let ranges: Vec<Range<u32>> = lines.iter().flat_map(|l| l.elves.clone()).collect();
+
+    match mode {
+        Mode::Count => count(&lines),
+        Mode::Classify => classify(&lines),
+        Mode::Redundant => redundant(&lines),
+        Mode::Plan { weighted } => plan::print(&lines, weighted),
+        Mode::Coverage => coverage::print(&ranges),
+        Mode::Draw(style, width) => draw::print(&lines, style, width),
+        Mode::Pairs => {
+            let counts = sweep::count(&ranges);
+            println!("{}", counts.containing);
+            println!("{}", counts.overlapping);
+        }
+    }
 }
@@ -0,0 +1,106 @@
+use crate::{assignments::Line, range::Range};
+
+struct Elf {
+    line: usize,
+    index: usize,
+    range: Range<u32>,
+}
+
+/// Keeps the most elves: always keep the elf that finishes first among
+/// those that don't overlap what is already kept.
+fn most_elves(elves: &[Elf]) -> Vec<bool> {
+    let mut order: Vec<usize> = (0..elves.len()).collect();
+    order.sort_by_key(|&i| elves[i].range.end);
+
+    let mut kept = vec![false; elves.len()];
+    let mut last_end = None;
+    for i in order {
+        let range = &elves[i].range;
+        if last_end.is_none_or(|end| end < range.start) {
+            kept[i] = true;
+            last_end = Some(range.end);
+        }
+    }
+    kept
+}
+
+/// Keeps the most sections, so that dropping an elf costs its range length.
+/// `best[j]` is the most sections the first `j` elves by end can keep.
+fn most_sections(elves: &[Elf]) -> Vec<bool> {
+    let mut order: Vec<usize> = (0..elves.len()).collect();
+    order.sort_by_key(|&i| elves[i].range.end);
+    let ends: Vec<u32> = order.iter().map(|&i| elves[i].range.end).collect();
+
+    // How many elves by end finish before each one starts.
+    let before: Vec<usize> = order
+        .iter()
+        .map(|&i| ends.partition_point(|&end| end < elves[i].range.start))
+        .collect();
+
+    let mut best = vec![0; order.len() + 1];
+    for (j, &i) in order.iter().enumerate() {
+        best[j + 1] = best[j].max(best[before[j]] + elves[i].range.len());
+    }
+
+    let mut kept = vec![false; elves.len()];
+    let mut j = order.len();
+    while j > 0 {
+        let i = order[j - 1];
+        if best[j] == best[j - 1] {
+            j -= 1;
+        } else {
+            kept[i] = true;
+            j = before[j - 1];
+        }
+    }
+    kept
+}
+
+fn describe(elf: &Elf) -> String {
+    format!("line {} elf {} ({})", elf.line, elf.index + 1, elf.range)
+}
+
+/// Plans which elves to keep so that no two kept assignments overlap,
+/// dropping as few elves as possible, or as few sections when `weighted`.
+pub fn print(lines: &[Line<u32>], weighted: bool) {
+    let elves: Vec<Elf> = lines
+        .iter()
+        .flat_map(|line| {
+            line.elves.iter().enumerate().map(|(index, &range)| Elf {
+                line: line.line,
+                index,
+                range,
+            })
+        })
+        .collect();
+
+    let kept = if weighted {
+        most_sections(&elves)
+    } else {
+        most_elves(&elves)
+    };
+
+    let (mut keep, mut drop) = (vec![], vec![]);
+    for (elf, &kept) in elves.iter().zip(&kept) {
+        if kept {
+            keep.push(elf);
+        } else {
+            drop.push(elf);
+        }
+    }
+
+    let sections = |elves: &[&Elf]| elves.iter().map(|e| e.range.len()).sum::<u64>();
+    println!(
+        "keep {} elves ({} sections), drop {} elves ({} sections)",
+        keep.len(),
+        sections(&keep),
+        drop.len(),
+        sections(&drop)
+    );
+    for elf in keep {
+        println!("keep {}", describe(elf));
+    }
+    for elf in drop {
+        println!("drop {}", describe(elf));
+    }
+}
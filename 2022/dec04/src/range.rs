@@ -0,0 +1,96 @@
+use std::{fmt, str::FromStr};
+
+/// The sections from `start` to `end`, both included. Parsing rejects ranges
+/// that end before they start.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Range<T> {
+    pub start: T,
+    pub end: T,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Relation {
+    Disjoint,
+    Overlapping,
+    LeftContainsRight,
+    RightContainsLeft,
+    Equal,
+}
+
+impl Relation {
+    pub const ALL: [Relation; 5] = [
+        Relation::Disjoint,
+        Relation::Overlapping,
+        Relation::LeftContainsRight,
+        Relation::RightContainsLeft,
+        Relation::Equal,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Relation::Disjoint => "disjoint",
+            Relation::Overlapping => "overlapping",
+            Relation::LeftContainsRight => "left-contains-right",
+            Relation::RightContainsLeft => "right-contains-left",
+            Relation::Equal => "equal",
+        }
+    }
+}
+
+impl Range<u32> {
+    /// Number of sections in the range.
+    pub fn len(&self) -> u64 {
+        (self.end - self.start) as u64 + 1
+    }
+}
+
+impl<T: Ord> Range<T> {
+    pub fn contains(&self, other: &Range<T>) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+    pub fn has_overlap(&self, other: &Range<T>) -> bool {
+        !(self.end < other.start || other.end < self.start)
+    }
+    /// Classifies a pair, with containment taking precedence over a partial
+    /// overlap.
+    pub fn relation(&self, other: &Range<T>) -> Relation {
+        match (self.contains(other), other.contains(self)) {
+            (true, true) => Relation::Equal,
+            (true, false) => Relation::LeftContainsRight,
+            (false, true) => Relation::RightContainsLeft,
+            (false, false) if self.has_overlap(other) => Relation::Overlapping,
+            (false, false) => Relation::Disjoint,
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Range<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+impl<T: Ord + FromStr> FromStr for Range<T> {
+    type Err = String;
+
+    /// Reads `START-END`. The dash is looked for after the first character so
+    /// that a negative start still parses.
+    fn from_str(fromto: &str) -> Result<Range<T>, String> {
+        let dash = fromto
+            .char_indices()
+            .skip(1)
+            .find(|&(_, c)| c == '-')
+            .map(|(i, _)| i)
+            .ok_or_else(|| format!("expected START-END, got {:?}", fromto))?;
+        let (start, end) = (&fromto[..dash], &fromto[dash + 1..]);
+
+        let number = |s: &str| {
+            str::parse::<T>(s).map_err(|_| format!("{:?} is not a section in {:?}", s, fromto))
+        };
+        let (start, end) = (number(start)?, number(end)?);
+        if end < start {
+            return Err(format!("{:?} ends before it starts", fromto));
+        }
+        Ok(Range { start, end })
+    }
+}
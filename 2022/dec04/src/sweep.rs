@@ -0,0 +1,139 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use crate::range::Range;
+
+/// Pairs of elves anywhere in the file, not just on the same line.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Counts {
+    pub overlapping: u64,
+    /// Pairs where one range contains the other, counting equal ranges once.
+    pub containing: u64,
+}
+
+/// Counts of ends seen so far, indexed by rank among all ends.
+struct Fenwick(Vec<u64>);
+
+impl Fenwick {
+    fn add(&mut self, rank: usize) {
+        let mut i = rank + 1;
+        while i < self.0.len() {
+            self.0[i] += 1;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// Number of ends with rank below `rank`.
+    fn below(&self, rank: usize) -> u64 {
+        let mut sum = 0;
+        let mut i = rank;
+        while i > 0 {
+            sum += self.0[i];
+            i -= i & i.wrapping_neg();
+        }
+        sum
+    }
+}
+
+/// Sweeps the ranges by start. A range overlaps every earlier range that
+/// hasn't ended before it starts, and is contained in every earlier range
+/// that ends no sooner; ranges starting together are visited longest first.
+pub fn count<T: Ord + Copy>(ranges: &[Range<T>]) -> Counts {
+    let mut sorted = ranges.to_vec();
+    sorted.sort_unstable_by_key(|r| (r.start, Reverse(r.end)));
+
+    let mut ends: Vec<T> = sorted.iter().map(|r| r.end).collect();
+    ends.sort_unstable();
+    ends.dedup();
+
+    let mut counts = Counts::default();
+    let mut active = BinaryHeap::new();
+    let mut seen = Fenwick(vec![0; ends.len() + 1]);
+
+    for (i, range) in sorted.iter().enumerate() {
+        while active.peek().is_some_and(|&Reverse(end)| end < range.start) {
+            active.pop();
+        }
+        counts.overlapping += active.len() as u64;
+        active.push(Reverse(range.end));
+
+        let rank = ends.binary_search(&range.end).unwrap();
+        counts.containing += i as u64 - seen.below(rank);
+        seen.add(rank);
+    }
+
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks every pair, for comparing against `count`.
+    fn brute_force<T: Ord>(ranges: &[Range<T>]) -> Counts {
+        let mut counts = Counts::default();
+        for (i, a) in ranges.iter().enumerate() {
+            for b in &ranges[i + 1..] {
+                if a.has_overlap(b) {
+                    counts.overlapping += 1;
+                }
+                if a.contains(b) || b.contains(a) {
+                    counts.containing += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    struct Rng(u64);
+
+    impl Rng {
+        fn below(&mut self, n: u64) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 % n
+        }
+    }
+
+    #[test]
+    fn sweep_matches_brute_force_on_random_inputs() {
+        let mut rng = Rng(2022);
+        for round in 0..1000 {
+            let n = rng.below(200) as usize;
+            let width = 1 + rng.below(100) as u32;
+            let ranges: Vec<Range<u32>> = (0..n)
+                .map(|_| {
+                    let start = 1 + rng.below(width as u64) as u32;
+                    let end = start + rng.below((width - start + 1) as u64) as u32;
+                    Range { start, end }
+                })
+                .collect();
+
+            assert_eq!(
+                count(&ranges),
+                brute_force(&ranges),
+                "round {}: sweep and brute force disagree on {:?}",
+                round,
+                ranges
+            );
+        }
+    }
+
+    #[test]
+    fn sweep_matches_brute_force_on_edge_cases() {
+        let cases: &[&[(u32, u32)]] = &[
+            &[],
+            &[(1, 1)],
+            &[(3, 3), (3, 3), (3, 3)],
+            &[(1, 5), (1, 5), (2, 4), (5, 9)],
+            &[(0, u32::MAX), (u32::MAX, u32::MAX), (0, 0)],
+        ];
+        for case in cases {
+            let ranges: Vec<Range<u32>> = case
+                .iter()
+                .map(|&(start, end)| Range { start, end })
+                .collect();
+            assert_eq!(count(&ranges), brute_force(&ranges), "{:?}", ranges);
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use std::fmt;
+
+#[derive(Debug)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub reason: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.reason
+        )
+    }
+}
+
+/// A crate label or stack number with the columns it spans, both included
+/// and counted from 1.
+struct Token {
+    first: usize,
+    last: usize,
+    text: String,
+}
+
+fn error(line: usize, column: usize, reason: String) -> ParseError {
+    ParseError {
+        line,
+        column,
+        reason,
+    }
+}
+
+/// The stack numbers of the footer, which must count up from 1.
+fn footer(line_no: usize, text: &str) -> Result<Vec<Token>, ParseError> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+
+    while i < chars.len() {
+        if chars[i] == ' ' {
+            i += 1;
+            continue;
+        }
+        let first = i;
+        while i < chars.len() && chars[i] != ' ' {
+            i += 1;
+        }
+        let text: String = chars[first..i].iter().collect();
+
+        let expected = tokens.len() + 1;
+        if str::parse::<usize>(&text) != Ok(expected) {
+            return Err(error(
+                line_no,
+                first + 1,
+                format!("expected stack number {}, got {:?}", expected, text),
+            ));
+        }
+        tokens.push(Token {
+            first: first + 1,
+            last: i,
+            text,
+        });
+    }
+
+    if tokens.is_empty() {
+        return Err(error(line_no, 1, "expected stack numbers".to_string()));
+    }
+    Ok(tokens)
+}
+
+/// The `[label]` crates of a row, separated by any number of spaces.
+fn crates(line_no: usize, text: &str) -> Result<Vec<Token>, ParseError> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            ' ' => i += 1,
+            '[' => {
+                let first = i;
+                let close = chars[first + 1..]
+                    .iter()
+                    .position(|&c| c == ']' || c == '[' || c == ' ')
+                    .map(|n| first + 1 + n)
+                    .filter(|&n| chars[n] == ']')
+                    .ok_or_else(|| error(line_no, first + 1, "unclosed crate".to_string()))?;
+                if close == first + 1 {
+                    return Err(error(
+                        line_no,
+                        first + 1,
+                        "crate without a label".to_string(),
+                    ));
+                }
+                tokens.push(Token {
+                    first: first + 1,
+                    last: close + 1,
+                    text: chars[first + 1..close].iter().collect(),
+                });
+                i = close + 1;
+            }
+            c => {
+                return Err(error(
+                    line_no,
+                    i + 1,
+                    format!("expected a crate or a space, got {:?}", c),
+                ))
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Reads a drawing of crates above a footer of stack numbers. Each crate
+/// belongs to the one stack number it is drawn above, so stacks can be
+/// numbered past 9 and labels can be longer than a letter. `lines` is the
+/// drawing without the blank line after it, starting at input line 1.
+/// Returns every stack from the bottom up.
+pub fn parse(lines: &[String]) -> Result<Vec<Vec<String>>, ParseError> {
+    let (footer_line, rows) = match lines.split_last() {
+        None => return Err(error(1, 1, "expected a drawing".to_string())),
+        Some(split) => split,
+    };
+    let numbers = footer(lines.len(), footer_line.trim_end())?;
+    let mut stacks: Vec<Vec<String>> = vec![vec![]; numbers.len()];
+
+    for (height, (idx, row)) in rows.iter().enumerate().rev().enumerate() {
+        let line_no = idx + 1;
+        let mut seen = vec![false; numbers.len()];
+
+        for c in crates(line_no, row.trim_end())? {
+            let below: Vec<usize> = (0..numbers.len())
+                .filter(|&s| numbers[s].first <= c.last && c.first <= numbers[s].last)
+                .collect();
+            let s = match below[..] {
+                [s] => s,
+                [] => {
+                    return Err(error(
+                        line_no,
+                        c.first,
+                        format!("crate [{}] is not above a stack number", c.text),
+                    ))
+                }
+                _ => {
+                    let spanned: Vec<&str> =
+                        below.iter().map(|&s| numbers[s].text.as_str()).collect();
+                    return Err(error(
+                        line_no,
+                        c.first,
+                        format!("crate [{}] spans stacks {}", c.text, spanned.join(", ")),
+                    ));
+                }
+            };
+
+            if seen[s] {
+                return Err(error(
+                    line_no,
+                    c.first,
+                    format!("second crate above stack {}", numbers[s].text),
+                ));
+            }
+            seen[s] = true;
+            if stacks[s].len() != height {
+                return Err(error(
+                    line_no,
+                    c.first,
+                    format!(
+                        "crate [{}] floats above a gap in stack {}",
+                        c.text, numbers[s].text
+                    ),
+                ));
+            }
+            stacks[s].push(c.text);
+        }
+    }
+
+    Ok(stacks)
+}
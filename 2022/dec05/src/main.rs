@@ -1,62 +1,50 @@
-use std::{io::stdin, str::Chars};
+mod drawing;
 
-#[derive(Debug)]
-struct Row {
-    pub crates: Vec<Option<char>>,
+use std::{env::args, io::stdin, process::exit};
+
+#[derive(Debug, Clone)]
+struct Stacks {
+    pub stacks: Vec<Vec<String>>,
 }
 
-impl Row {
-    fn get(cs: &mut Chars) -> Option<Option<char>> {
-        match (cs.next(), cs.next(), cs.next(), cs.next()) {
-            (Some('['), letter, Some(']'), _) => Some(letter),
-            (Some(' '), Some(' '), Some(' '), _) => Some(None),
-            _ => None,
+impl Stacks {
+    fn apply(&mut self, cmd: &CommandMove, crane: Crane) {
+        let l = self.stacks[cmd.from - 1].len();
+        let mut moved: Vec<String> = self.stacks[cmd.from - 1].drain(l - cmd.count..).collect();
+        if crane == Crane::Mover9000 {
+            moved.reverse();
         }
+        self.stacks[cmd.to - 1].extend(moved);
     }
 
-    fn from_str(s: &str) -> Option<Row> {
-        let mut cs = s.chars();
-        let mut out = vec![];
-
-        let mut current = Self::get(&mut cs);
-        while current.is_some() {
-            out.push(current.unwrap());
-            current = Self::get(&mut cs);
-        }
-
-        if out.is_empty() {
-            None
-        } else {
-            Some(Row { crates: out })
-        }
+    fn top(&self) -> String {
+        self.stacks
+            .iter()
+            .map(|s| s.last().map_or(" ", String::as_str))
+            .collect()
     }
 }
 
-#[derive(Debug)]
-struct Stacks {
-    pub stacks: Vec<Vec<char>>,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Crane {
+    /// Moves one crate at a time, so moved crates end up in reverse order.
+    Mover9000,
+    /// Moves several crates at once, keeping their order.
+    Mover9001,
 }
 
-impl Stacks {
-    fn from_rows(rs: Vec<Row>) -> Stacks {
-        let mut stacks = vec![];
-        for _ in &rs[0].crates {
-            stacks.push(vec![]);
-        }
-
-        for r in &rs {
-            for (idx, c) in r.crates.iter().enumerate() {
-                if let Some(letter) = c {
-                    stacks[idx].push(*letter)
-                }
-            }
-        }
-
-        for s in stacks.iter_mut() {
-            s.reverse()
-        }
+impl Crane {
+    const ALL: [Crane; 2] = [Crane::Mover9000, Crane::Mover9001];
+}
 
-        Stacks { stacks }
+/// `dec05 [--crane 9000|9001]`, running both cranes by default.
+fn parse_args() -> Vec<Crane> {
+    let args: Vec<String> = args().skip(1).collect();
+    match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        [] => Crane::ALL.to_vec(),
+        ["--crane", "9000"] => vec![Crane::Mover9000],
+        ["--crane", "9001"] => vec![Crane::Mover9001],
+        _ => panic!("unknown arguments {:?}", args),
     }
 }
 
@@ -87,27 +75,30 @@ impl CommandMove {
 }
 
 fn main() {
-    let mut rows = vec![];
-    for line in stdin().lines().flatten() {
-        if let Some(row) = Row::from_str(line.as_str()) {
-            rows.push(row)
-        } else {
-            break;
+    let cranes = parse_args();
+
+    let drawing: Vec<String> = stdin()
+        .lines()
+        .map_while(Result::ok)
+        .take_while(|line| !line.trim().is_empty())
+        .collect();
+    let stacks = Stacks {
+        stacks: drawing::parse(&drawing).unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            exit(1);
+        }),
+    };
+    let cmds: Vec<CommandMove> = stdin()
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| CommandMove::from_str(&line))
+        .collect();
+
+    for crane in cranes {
+        let mut stacks = stacks.clone();
+        for cmd in &cmds {
+            stacks.apply(cmd, crane);
         }
+        println!("{}", stacks.top());
     }
-
-    let mut stacks = Stacks::from_rows(rows);
-
-    for line in stdin().lines().flatten() {
-        if let Some(cmd) = CommandMove::from_str(&line) {
-            let l = stacks.stacks[cmd.from - 1].len();
-            let moved: Vec<char> = stacks.stacks[cmd.from - 1].drain(l - cmd.count..).collect();
-            stacks.stacks[cmd.to - 1].extend(moved);
-        }
-    }
-
-    for s in stacks.stacks {
-        print!("{}", s.iter().last().unwrap())
-    }
-    println!();
 }